@@ -0,0 +1,36 @@
+//! The parallel path must not depend on thread count, cutoff placement or read size
+
+#![no_main]
+
+use blitzhash::{
+    blitz_hash, blitz_hash_parallel, blitz_hash_parallel_reader_with, blitz_hash_parallel_with,
+    PARALLEL_BLOCK_SIZE,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u64, u8, u16, u32, u32, Vec<u8>)| {
+    let (seed, threads, len_units, cutoff, read_size, pattern) = input;
+
+    // Fuzz inputs are short, so tile the pattern out to a length that crosses
+    // block boundaries and, occasionally, the default serial cutoff (~1.3 MB max)
+    let len = len_units as usize * 20;
+    let data: Vec<u8> = if pattern.is_empty() {
+        vec![0; len]
+    } else {
+        pattern.iter().copied().cycle().take(len).collect()
+    };
+
+    let threads = threads as usize % 16;
+    let cutoff = cutoff as usize % (len + 2);
+    let reference = blitz_hash_parallel_with(seed, &data, 1, cutoff);
+
+    assert_eq!(blitz_hash_parallel_with(seed, &data, threads, cutoff), reference);
+    if data.len() < cutoff {
+        assert_eq!(reference, blitz_hash(seed, &data));
+    }
+
+    let expected = blitz_hash_parallel(seed, &data, threads);
+    let read_size = read_size as usize % (4 * PARALLEL_BLOCK_SIZE);
+    let streamed = blitz_hash_parallel_reader_with(seed, &data[..], threads, read_size).unwrap();
+    assert_eq!(streamed, expected);
+});
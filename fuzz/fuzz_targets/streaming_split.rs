@@ -0,0 +1,25 @@
+//! Streaming with arbitrary split points must match the one-shot digest
+
+#![no_main]
+
+use blitzhash::{blitz_hash, BlitzState};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u64, Vec<u16>, Vec<u8>)| {
+    let (seed, splits, data) = input;
+
+    let mut state = BlitzState::new(seed);
+    let mut rest = &data[..];
+    for split in splits {
+        let take = split as usize % (rest.len() + 1);
+        let (piece, tail) = rest.split_at(take);
+        state.absorb(piece);
+        rest = tail;
+    }
+    state.absorb(rest);
+
+    let snapshot = state.snapshot();
+    let streamed = state.finalize();
+    assert_eq!(streamed, blitz_hash(seed, &data));
+    assert_eq!(snapshot.finalize(&data[snapshot.absorbed() as usize..]), streamed);
+});
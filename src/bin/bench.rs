@@ -14,6 +14,9 @@ struct BenchConfig {
     threads: usize,
     seed: u64,
     repeat: usize,
+    sweep: bool,
+    sweep_max: usize,
+    sweep_parallel: bool,
 }
 
 impl Default for BenchConfig {
@@ -25,6 +28,9 @@ impl Default for BenchConfig {
             threads: 8,
             seed: 0,
             repeat: 3,
+            sweep: false,
+            sweep_max: 1_000_000_000, // 1 GB top of the sweep
+            sweep_parallel: false,
         }
     }
 }
@@ -39,10 +45,38 @@ struct BenchResult {
     digest_hex: String,
 }
 
+/// Smallest input in a `--sweep` run.
+const SWEEP_MIN_SIZE: usize = 16;
+
+/// Each sweep point is 4x the previous one.
+const SWEEP_FACTOR: usize = 4;
+
+/// Bytes hashed per timed sweep sample, so tiny inputs loop long enough to measure.
+const SWEEP_TARGET_BYTES: usize = 64_000_000;
+
+/// Keys hashed per timed small-key run; lengths cycle through 1..=`SMALL_KEY_MAX` bytes.
+const SMALL_KEYS: usize = 4_000_000;
+
+/// Longest small key, the top of the `blitz_hash64` short-input path.
+const SMALL_KEY_MAX: usize = 16;
+
+/// Small keys are cut from this many leading bytes of the input (zero-padded).
+const SMALL_KEY_POOL: usize = 4096;
+
+struct SweepResult {
+    algorithm: String,
+    threads: usize,
+    size: usize,
+    iters: usize,
+    seed: u64,
+    mb_per_sec: f64,
+}
+
 fn parse_args() -> BenchConfig {
     let mut config = BenchConfig::default();
     let args: Vec<String> = std::env::args().collect();
     let mut i = 1;
+    let mut size_given = false;
 
     while i < args.len() {
         match args[i].as_str() {
@@ -53,6 +87,7 @@ fn parse_args() -> BenchConfig {
             "--size" => {
                 i += 1;
                 config.size = args[i].parse().expect("Invalid size");
+                size_given = true;
             }
             "--chunk" => {
                 i += 1;
@@ -70,6 +105,17 @@ fn parse_args() -> BenchConfig {
                 i += 1;
                 config.repeat = args[i].parse().expect("Invalid repeat count");
             }
+            "--sweep" => {
+                config.sweep = true;
+            }
+            "--sweep-max" => {
+                i += 1;
+                config.sweep_max = args[i].parse().expect("Invalid sweep max size");
+            }
+            "--sweep-parallel" => {
+                config.sweep = true;
+                config.sweep_parallel = true;
+            }
             _ => {
                 eprintln!("Unknown option: {}", args[i]);
                 std::process::exit(1);
@@ -78,6 +124,11 @@ fn parse_args() -> BenchConfig {
         i += 1;
     }
 
+    if config.sweep && size_given {
+        eprintln!("--size cannot be combined with --sweep; use --sweep-max to set the top sweep size");
+        std::process::exit(1);
+    }
+
     config
 }
 
@@ -122,7 +173,7 @@ fn bench_sha256_streaming(data: &[u8], chunk_size: usize) -> (f64, String) {
 fn bench_blitzhash_single(data: &[u8], _chunk_size: usize, seed: u64) -> (f64, String) {
     let start = Instant::now();
     
-    // Use optimized one-shot (no fake SIMD)
+    // Use optimized one-shot on whatever backend is compiled in
     let result = blitzhash::blitz_hash(seed, data);
     
     let elapsed = start.elapsed().as_secs_f64();
@@ -132,6 +183,43 @@ fn bench_blitzhash_single(data: &[u8], _chunk_size: usize, seed: u64) -> (f64, S
     (mb_per_sec, digest)
 }
 
+fn bench_blitzhash_stream(data: &[u8], chunk_size: usize, seed: u64) -> (f64, String) {
+    let start = Instant::now();
+    let mut hasher = blitzhash::BlitzState::new(seed);
+    
+    for chunk in data.chunks(chunk_size) {
+        hasher.absorb(chunk);
+    }
+    
+    let result = hasher.finalize();
+    let elapsed = start.elapsed().as_secs_f64();
+    let mb_per_sec = (data.len() as f64 / 1_000_000.0) / elapsed;
+    let digest = hex::encode(&result[..8]);
+    
+    (mb_per_sec, digest)
+}
+
+/// Hashes `SMALL_KEYS` keys of 1-16 bytes with `blitz_hash64`; returns MB/s, ns/key and
+/// the XOR of all key hashes.
+fn bench_blitzhash_small_keys(data: &[u8], seed: u64) -> (f64, f64, String) {
+    let pool: Vec<u8> = data.iter().copied().chain(std::iter::repeat(0)).take(SMALL_KEY_POOL).collect();
+    let start = Instant::now();
+    let mut acc = 0u64;
+    let mut bytes = 0;
+
+    for i in 0..SMALL_KEYS {
+        let len = i % SMALL_KEY_MAX + 1;
+        let pos = (i * 7) % (SMALL_KEY_POOL - SMALL_KEY_MAX);
+        acc ^= blitzhash::blitz_hash64(seed, std::hint::black_box(&pool[pos..pos + len]));
+        bytes += len;
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+    let mb_per_sec = (bytes as f64 / 1_000_000.0) / elapsed;
+    let ns_per_key = elapsed * 1e9 / SMALL_KEYS as f64;
+    (mb_per_sec, ns_per_key, hex::encode(acc.to_le_bytes()))
+}
+
 fn bench_blitzhash_parallel(data: &[u8], threads: usize, seed: u64) -> (f64, String) {
     let start = Instant::now();
     let result = blitzhash::blitz_hash_parallel(seed, data, threads);
@@ -142,6 +230,12 @@ fn bench_blitzhash_parallel(data: &[u8], threads: usize, seed: u64) -> (f64, Str
     (mb_per_sec, digest)
 }
 
+/// Median of the timed runs (upper median for an even count).
+fn median(mut speeds: Vec<f64>) -> f64 {
+    speeds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    speeds[speeds.len() / 2]
+}
+
 fn run_benchmark(config: &BenchConfig, data: &[u8]) -> Vec<BenchResult> {
     let mut results = Vec::new();
     
@@ -158,6 +252,7 @@ fn run_benchmark(config: &BenchConfig, data: &[u8]) -> Vec<BenchResult> {
     std::io::stdout().flush().unwrap();
     let _ = bench_sha256_streaming(data, config.chunk);
     let _ = bench_blitzhash_single(data, config.chunk, config.seed);
+    let _ = bench_blitzhash_stream(data, config.chunk, config.seed);
     println!("done\n");
 
     // SHA-256 baseline (single-threaded)
@@ -181,12 +276,12 @@ fn run_benchmark(config: &BenchConfig, data: &[u8]) -> Vec<BenchResult> {
             });
         }
     }
-    sha_speeds.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let sha_median = sha_speeds[sha_speeds.len() / 2];
+    let sha_median = median(sha_speeds);
     println!("   Median: {:.2} MB/s\n", sha_median);
 
     // BlitzHash single-threaded
-    println!("📊 Running BlitzHash-SIMD (single-threaded)...");
+    let single_label = format!("BlitzHash-{}", blitzhash::backend_name());
+    println!("📊 Running {} (single-threaded)...", single_label);
     let mut blitz_single_speeds = Vec::new();
     for i in 0..config.repeat {
         print!("   Run {}/{}: ", i + 1, config.repeat);
@@ -196,7 +291,7 @@ fn run_benchmark(config: &BenchConfig, data: &[u8]) -> Vec<BenchResult> {
         println!("{:.2} MB/s (digest: {}...)", speed, &digest[..16]);
         if i == 0 {
             results.push(BenchResult {
-                algorithm: "BlitzHash-SIMD".to_string(),
+                algorithm: single_label.clone(),
                 threads: 1,
                 chunk: config.chunk,
                 size: data.len(),
@@ -206,11 +301,63 @@ fn run_benchmark(config: &BenchConfig, data: &[u8]) -> Vec<BenchResult> {
             });
         }
     }
-    blitz_single_speeds.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let blitz_single_median = blitz_single_speeds[blitz_single_speeds.len() / 2];
+    let blitz_single_median = median(blitz_single_speeds);
     println!("   Median: {:.2} MB/s ({}x SHA-256)\n", 
              blitz_single_median, blitz_single_median / sha_median);
 
+    // BlitzHash streaming, `config.chunk` bytes per absorb
+    println!("📊 Running BlitzHash-Stream ({} byte absorbs)...", config.chunk);
+    let (_, single_digest) = bench_blitzhash_single(data, config.chunk, config.seed);
+    let mut blitz_stream_speeds = Vec::new();
+    for i in 0..config.repeat {
+        print!("   Run {}/{}: ", i + 1, config.repeat);
+        std::io::stdout().flush().unwrap();
+        let (speed, digest) = bench_blitzhash_stream(data, config.chunk, config.seed);
+        blitz_stream_speeds.push(speed);
+        println!("{:.2} MB/s (digest: {}...)", speed, &digest[..16]);
+        if digest != single_digest {
+            println!("   ⚠️  streaming digest differs from one-shot ({}...)", &single_digest[..16]);
+        }
+        if i == 0 {
+            results.push(BenchResult {
+                algorithm: "BlitzHash-Stream".to_string(),
+                threads: 1,
+                chunk: config.chunk,
+                size: data.len(),
+                seed: config.seed,
+                mb_per_sec: speed,
+                digest_hex: digest,
+            });
+        }
+    }
+    let blitz_stream_median = median(blitz_stream_speeds);
+    println!("   Median: {:.2} MB/s ({:.2}x one-shot)\n", 
+             blitz_stream_median, blitz_stream_median / blitz_single_median);
+
+    // BlitzHash on hashmap-sized keys (the <= 16 byte path)
+    println!("📊 Running BlitzHash-Small ({} keys of 1-16 bytes)...", SMALL_KEYS);
+    let mut blitz_small_speeds = Vec::new();
+    for i in 0..config.repeat {
+        print!("   Run {}/{}: ", i + 1, config.repeat);
+        std::io::stdout().flush().unwrap();
+        let (speed, ns_per_key, digest) = bench_blitzhash_small_keys(data, config.seed);
+        blitz_small_speeds.push(speed);
+        println!("{:.2} MB/s, {:.2} ns/key (digest: {}...)", speed, ns_per_key, digest);
+        if i == 0 {
+            results.push(BenchResult {
+                algorithm: "BlitzHash-Small".to_string(),
+                threads: 1,
+                // Keys per run and the longest key; this row never touches `data.len()` bytes
+                chunk: SMALL_KEYS,
+                size: SMALL_KEY_MAX,
+                seed: config.seed,
+                mb_per_sec: speed,
+                digest_hex: digest,
+            });
+        }
+    }
+    println!("   Median: {:.2} MB/s\n", median(blitz_small_speeds));
+
     // BlitzHash parallel
     println!("📊 Running BlitzHash (parallel, {} threads)...", config.threads);
     let mut blitz_parallel_speeds = Vec::new();
@@ -232,8 +379,7 @@ fn run_benchmark(config: &BenchConfig, data: &[u8]) -> Vec<BenchResult> {
             });
         }
     }
-    blitz_parallel_speeds.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let blitz_parallel_median = blitz_parallel_speeds[blitz_parallel_speeds.len() / 2];
+    let blitz_parallel_median = median(blitz_parallel_speeds);
     println!("   Median: {:.2} MB/s ({}x SHA-256)\n", 
              blitz_parallel_median, blitz_parallel_median / sha_median);
 
@@ -241,24 +387,25 @@ fn run_benchmark(config: &BenchConfig, data: &[u8]) -> Vec<BenchResult> {
 }
 
 fn print_results_table(results: &[BenchResult]) {
-    println!("\n╔═══════════════════════════════════════════════════════════╗");
-    println!("║                    BENCHMARK RESULTS                      ║");
-    println!("╠═══════════════════════════════════════════════════════════╣");
-    println!("║ Algorithm         │ Threads │  Chunk  │    MB/s │ Speedup ║");
-    println!("╠═══════════════════════════════════════════════════════════╣");
+    println!("\n╔══════════════════════════════════════════════════════════════════════════════╗");
+    println!("║                              BENCHMARK RESULTS                               ║");
+    println!("╠══════════════════════════════════════════════════════════════════════════════╣");
+    println!("║ Algorithm         │ Threads │  Chunk  │    MB/s │ Speedup │ Digest           ║");
+    println!("╠══════════════════════════════════════════════════════════════════════════════╣");
     
     let baseline = results[0].mb_per_sec;
     for result in results {
         let speedup = result.mb_per_sec / baseline;
-        println!("║ {:16} │ {:7} │ {:7} │ {:7.2} │ {:6.2}x ║",
+        println!("║ {:16} │ {:7} │ {:7} │ {:7.2} │ {:6.2}x │ {:16} ║",
                  result.algorithm,
                  result.threads,
-                 format!("{}K", result.chunk / 1024),
+                 if result.chunk % 1024 == 0 { format!("{}K", result.chunk / 1024) } else { result.chunk.to_string() },
                  result.mb_per_sec,
-                 speedup);
+                 speedup,
+                 &result.digest_hex[..16]);
     }
     
-    println!("╚═══════════════════════════════════════════════════════════╝\n");
+    println!("╚══════════════════════════════════════════════════════════════════════════════╝\n");
 }
 
 fn append_to_csv(results: &[BenchResult]) {
@@ -294,15 +441,151 @@ fn append_to_csv(results: &[BenchResult]) {
     println!("✅ Results appended to bench_results.csv");
 }
 
+/// Geometric size series from `min` up to `max`, multiplying by `factor` each step.
+/// `max` is always the last point, even when it isn't an exact power step.
+fn sweep_sizes(min: usize, max: usize, factor: usize) -> Vec<usize> {
+    assert!(min >= 1 && factor >= 2, "sweep needs min >= 1 and factor >= 2");
+    let mut sizes = Vec::new();
+    let mut size = min;
+    while size < max {
+        sizes.push(size);
+        size = match size.checked_mul(factor) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    sizes.push(max.max(min));
+    sizes
+}
+
+/// Times `iters` back-to-back hashes of `data` and returns MB/s.
+fn time_sweep_point<F: Fn(&[u8]) -> [u8; 32]>(data: &[u8], iters: usize, hash: &F) -> f64 {
+    let start = Instant::now();
+    for _ in 0..iters {
+        std::hint::black_box(hash(std::hint::black_box(data)));
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    ((data.len() * iters) as f64 / 1_000_000.0) / elapsed
+}
+
+/// Sweeps one hash variant over every prefix size of `data`, appending a row per size.
+fn sweep_variant<F: Fn(&[u8]) -> [u8; 32]>(
+    label: &str,
+    threads: usize,
+    hash: F,
+    sizes: &[usize],
+    data: &[u8],
+    config: &BenchConfig,
+    results: &mut Vec<SweepResult>,
+) {
+    println!("📊 Sweeping {}...", label);
+    for &size in sizes {
+        let slice = &data[..size];
+        let iters = (SWEEP_TARGET_BYTES / size).max(1);
+
+        // Warm-up
+        let _ = time_sweep_point(slice, iters, &hash);
+        let speeds = (0..config.repeat)
+            .map(|_| time_sweep_point(slice, iters, &hash))
+            .collect();
+        let speed = median(speeds);
+        println!("   {:>12} B: {:10.2} MB/s", size, speed);
+
+        results.push(SweepResult {
+            algorithm: label.to_string(),
+            threads,
+            size,
+            iters,
+            seed: config.seed,
+            mb_per_sec: speed,
+        });
+    }
+    println!();
+}
+
+fn run_sweep(config: &BenchConfig, data: &[u8]) -> Vec<SweepResult> {
+    if data.is_empty() {
+        eprintln!("Nothing to sweep: input is empty");
+        std::process::exit(1);
+    }
+    let min_size = SWEEP_MIN_SIZE.min(data.len());
+    let sizes = sweep_sizes(min_size, data.len(), SWEEP_FACTOR);
+    let seed = config.seed;
+    let threads = config.threads;
+
+    println!("\n🔥 SWEEP CONFIGURATION");
+    println!("   Sizes: {} points, {} B to {} B (x{})", sizes.len(), min_size, data.len(), SWEEP_FACTOR);
+    println!("   Threads: {}", if config.sweep_parallel { threads } else { 1 });
+    println!("   Seed: {}", seed);
+    println!("   Repeats: {}", config.repeat);
+    println!();
+
+    // Each variant is its own monomorphised call, so no point pays for an indirect call.
+    let mut results = Vec::new();
+    let single_label = format!("BlitzHash-{}", blitzhash::backend_name());
+    sweep_variant(&single_label, 1, |d: &[u8]| blitzhash::blitz_hash(seed, d), &sizes, data, config, &mut results);
+    if config.sweep_parallel {
+        sweep_variant("BlitzHash-MT", threads, |d: &[u8]| blitzhash::blitz_hash_parallel(seed, d, threads),
+                      &sizes, data, config, &mut results);
+    }
+
+    results
+}
+
+fn append_sweep_to_csv(results: &[SweepResult]) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let file_exists = std::path::Path::new("sweep_results.csv").exists();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open("sweep_results.csv")
+        .expect("Failed to open CSV file");
+
+    if !file_exists {
+        writeln!(file, "algorithm,threads,size,iters,seed,mb_s,timestamp")
+            .expect("Failed to write CSV header");
+    }
+
+    for result in results {
+        writeln!(file, "{},{},{},{},{},{:.2},{}",
+                 result.algorithm,
+                 result.threads,
+                 result.size,
+                 result.iters,
+                 result.seed,
+                 result.mb_per_sec,
+                 timestamp)
+            .expect("Failed to write CSV row");
+    }
+
+    println!("✅ Results appended to sweep_results.csv");
+}
+
 fn main() {
     println!("\n╔═══════════════════════════════════════════════════════════╗");
-    println!("║                      BLITZHASH v0.1                       ║");
+    println!("║                      BLITZHASH v0.2                       ║");
     println!("║            High-Performance Hash Benchmark                ║");
     println!("║                                                           ║");
     println!("║  ⚠️  NOT CRYPTOGRAPHICALLY SECURE - DEMO ONLY ⚠️           ║");
     println!("╚═══════════════════════════════════════════════════════════╝\n");
 
-    let config = parse_args();
+    let mut config = parse_args();
+
+    if config.sweep {
+        // One buffer at the top size; every sweep point hashes a prefix of it.
+        config.size = config.sweep_max;
+        let data = load_or_generate_data(&config);
+        let results = run_sweep(&config, &data);
+        append_sweep_to_csv(&results);
+        println!("\n🎉 Sweep complete!");
+        println!();
+        return;
+    }
+
     let data = load_or_generate_data(&config);
     let results = run_benchmark(&config, &data);
     
@@ -315,4 +598,29 @@ fn main() {
     println!("  2. Try larger files: --size 1000000000 (1 GB)");
     println!("  3. Experiment with: --threads <n> --chunk <bytes>");
     println!();
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sweep_sizes_geometric() {
+        let sizes = sweep_sizes(16, 1_000_000_000, 4);
+        assert_eq!(sizes[0], 16);
+        assert_eq!(*sizes.last().unwrap(), 1_000_000_000);
+        for pair in sizes.windows(2) {
+            assert!(pair[0] < pair[1]);
+            assert!(pair[1] <= pair[0] * 4);
+        }
+        assert_eq!(sizes.len(), 14);
+    }
+
+    #[test]
+    fn test_sweep_sizes_edges() {
+        assert_eq!(sweep_sizes(16, 64, 2), vec![16, 32, 64]);
+        assert_eq!(sweep_sizes(16, 16, 4), vec![16]);
+        assert_eq!(sweep_sizes(16, 8, 4), vec![16]);
+        assert_eq!(sweep_sizes(1, usize::MAX, 2).last(), Some(&usize::MAX));
+    }
+}
@@ -0,0 +1,439 @@
+//! blitzsum - sha256sum-style checksums with BlitzHash
+//! NOT for integrity against tampering - BlitzHash is not cryptographically secure
+
+use blitzhash::{BlitzDigest, BlitzState};
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "usage: blitzsum [--seed N] [FILE...]\n       blitzsum [--seed N] --check SUMFILE";
+
+/// One line of a checksum file
+#[derive(Debug, PartialEq, Eq)]
+enum CheckLine<'a> {
+    /// Blank line or `#` comment
+    Skip,
+    /// `<64-hex>  <path>` or `<64-hex> *<path>`, path already unescaped
+    Entry { digest: BlitzDigest, path: Cow<'a, [u8]> },
+    Malformed,
+}
+
+/// Escape a path for a checksum line, as coreutils does - `None` if it needs none
+///
+/// `\`, newline and carriage return become `\\`, `\n` and `\r`; the line then starts
+/// with a `\` so `--check` knows to undo it.
+fn escape_path(path: &[u8]) -> Option<Vec<u8>> {
+    if !path.iter().any(|b| matches!(b, b'\\' | b'\n' | b'\r')) {
+        return None;
+    }
+    let mut out = Vec::with_capacity(path.len() + 8);
+    for &b in path {
+        match b {
+            b'\\' => out.extend_from_slice(b"\\\\"),
+            b'\n' => out.extend_from_slice(b"\\n"),
+            b'\r' => out.extend_from_slice(b"\\r"),
+            _ => out.push(b),
+        }
+    }
+    Some(out)
+}
+
+/// Undo `escape_path` - `None` on an unknown or dangling escape
+fn unescape_path(path: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(path.len());
+    let mut bytes = path.iter();
+    while let Some(&b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        out.push(match bytes.next()? {
+            b'\\' => b'\\',
+            b'n' => b'\n',
+            b'r' => b'\r',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+/// Write `path` the way it appears in a checksum line, escaped if it has to be
+fn write_path(out: &mut impl Write, path: &[u8]) -> io::Result<()> {
+    match escape_path(path) {
+        Some(escaped) => {
+            out.write_all(b"\\")?;
+            out.write_all(&escaped)
+        }
+        None => out.write_all(path),
+    }
+}
+
+/// Parse one line (without its `\n`) - paths are raw bytes and may hold spaces
+fn parse_check_line(line: &[u8]) -> CheckLine<'_> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    if line.iter().all(u8::is_ascii_whitespace) || line.starts_with(b"#") {
+        return CheckLine::Skip;
+    }
+
+    // A leading backslash marks an escaped path
+    let (escaped, line) = match line.strip_prefix(b"\\") {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    // 64 hex digits, a space, then ' ' (text mode) or '*' (binary mode), then the path
+    if line.len() < 67 || line[64] != b' ' || !matches!(line[65], b' ' | b'*') {
+        return CheckLine::Malformed;
+    }
+    let digest = match std::str::from_utf8(&line[..64]).ok().and_then(|hex| hex.parse().ok()) {
+        Some(digest) => digest,
+        None => return CheckLine::Malformed,
+    };
+    let path = &line[66..];
+    if !escaped {
+        return CheckLine::Entry { digest, path: Cow::Borrowed(path) };
+    }
+    match unescape_path(path) {
+        Some(path) => CheckLine::Entry { digest, path: Cow::Owned(path) },
+        None => CheckLine::Malformed,
+    }
+}
+
+/// Per-file results of a `--check` run
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CheckSummary {
+    ok: usize,
+    failed: usize,
+    unreadable: usize,
+    malformed: usize,
+}
+
+impl CheckSummary {
+    /// Success only if every line parsed, every file matched, and there was at least one
+    fn exit_code(&self) -> i32 {
+        let clean = self.failed == 0 && self.unreadable == 0 && self.malformed == 0;
+        if clean && self.ok > 0 {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+/// Hash a file the way `blitz_hash` hashes its contents, without reading it all in
+fn hash_reader<R: Read>(seed: u64, mut reader: R) -> io::Result<[u8; 32]> {
+    let mut state = BlitzState::new(seed);
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        match reader.read(&mut buf) {
+            Ok(0) => return Ok(state.finalize()),
+            Ok(n) => state.absorb(&buf[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+    PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// The raw bytes of `path`, the inverse of `path_from_bytes`
+#[cfg(unix)]
+fn path_bytes(path: &Path) -> Cow<'_, [u8]> {
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(path.as_os_str().as_bytes())
+}
+
+#[cfg(not(unix))]
+fn path_bytes(path: &Path) -> Cow<'_, [u8]> {
+    match path.to_string_lossy() {
+        Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+        Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+    }
+}
+
+/// Verify every entry of a checksum file, reporting `<path>: OK` / `<path>: FAILED ...`
+///
+/// Results go to `out`, diagnostics about the checksum file itself to `err`.
+fn run_check(
+    seed: u64,
+    sumfile: &Path,
+    contents: &[u8],
+    out: &mut impl Write,
+    err: &mut impl Write,
+) -> io::Result<CheckSummary> {
+    let mut summary = CheckSummary::default();
+
+    for (lineno, line) in contents.split(|&b| b == b'\n').enumerate() {
+        let (digest, path) = match parse_check_line(line) {
+            CheckLine::Skip => continue,
+            CheckLine::Entry { digest, path } => (digest, path),
+            CheckLine::Malformed => {
+                summary.malformed += 1;
+                writeln!(err, "blitzsum: {}:{}: improperly formatted checksum line", sumfile.display(), lineno + 1)?;
+                continue;
+            }
+        };
+
+        write_path(out, &path)?;
+        match File::open(path_from_bytes(&path)).and_then(|file| hash_reader(seed, file)) {
+            Ok(actual) if actual == digest.0 => {
+                summary.ok += 1;
+                writeln!(out, ": OK")?;
+            }
+            Ok(_) => {
+                summary.failed += 1;
+                writeln!(out, ": FAILED")?;
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                summary.unreadable += 1;
+                writeln!(out, ": FAILED: no such file")?;
+            }
+            Err(e) => {
+                summary.unreadable += 1;
+                writeln!(out, ": FAILED: {}", e)?;
+            }
+        }
+    }
+
+    writeln!(
+        out,
+        "blitzsum: {} OK, {} FAILED, {} unreadable, {} improperly formatted",
+        summary.ok, summary.failed, summary.unreadable, summary.malformed
+    )?;
+    Ok(summary)
+}
+
+/// Print `<64-hex>  <path>` for each file (stdin as `-` when none are given)
+///
+/// Paths holding `\\`, newlines or carriage returns are escaped - see `escape_path`.
+fn run_generate(seed: u64, files: &[PathBuf], out: &mut impl Write) -> io::Result<i32> {
+    if files.is_empty() {
+        let digest = hash_reader(seed, io::stdin().lock())?;
+        writeln!(out, "{}  -", BlitzDigest(digest))?;
+        return Ok(0);
+    }
+
+    let mut code = 0;
+    for name in files {
+        match File::open(name).and_then(|file| hash_reader(seed, file)) {
+            Ok(digest) => {
+                let raw = path_bytes(name);
+                let escaped = escape_path(&raw);
+                if escaped.is_some() {
+                    out.write_all(b"\\")?;
+                }
+                write!(out, "{}  ", BlitzDigest(digest))?;
+                out.write_all(escaped.as_deref().unwrap_or(&raw))?;
+                writeln!(out)?;
+            }
+            Err(e) => {
+                eprintln!("blitzsum: {}: {}", name.display(), e);
+                code = 1;
+            }
+        }
+    }
+    Ok(code)
+}
+
+fn main() {
+    // Paths stay as `OsString` - file names need not be UTF-8
+    let args: Vec<OsString> = std::env::args_os().skip(1).collect();
+    let mut seed = 0;
+    let mut check = None;
+    let mut files = Vec::new();
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i].to_str() {
+            Some("--seed") => {
+                i += 1;
+                seed = args.get(i).and_then(|s| s.to_str()?.parse().ok()).unwrap_or_else(|| {
+                    eprintln!("blitzsum: --seed needs a u64\n{}", USAGE);
+                    std::process::exit(2);
+                });
+            }
+            Some("-c" | "--check") => {
+                i += 1;
+                check = Some(args.get(i).map(PathBuf::from).unwrap_or_else(|| {
+                    eprintln!("blitzsum: --check needs a checksum file\n{}", USAGE);
+                    std::process::exit(2);
+                }));
+            }
+            Some("-h" | "--help") => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => files.push(PathBuf::from(&args[i])),
+        }
+        i += 1;
+    }
+
+    let mut out = io::stdout().lock();
+    let code = match check {
+        Some(sumfile) => {
+            let contents = std::fs::read(&sumfile).unwrap_or_else(|e| {
+                eprintln!("blitzsum: {}: {}", sumfile.display(), e);
+                std::process::exit(1);
+            });
+            let mut err = io::stderr().lock();
+            run_check(seed, &sumfile, &contents, &mut out, &mut err).map(|summary| summary.exit_code())
+        }
+        None => run_generate(seed, &files, &mut out),
+    };
+
+    match code {
+        Ok(code) => std::process::exit(code),
+        Err(e) => {
+            eprintln!("blitzsum: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &[u8]) -> CheckLine<'_> {
+        CheckLine::Entry { digest: BlitzDigest([0xab; 32]), path: Cow::Borrowed(path) }
+    }
+
+    #[test]
+    fn test_parse_check_line() {
+        let hex = "ab".repeat(32);
+
+        let text = format!("{}  my file.txt", hex);
+        assert_eq!(parse_check_line(text.as_bytes()), entry(b"my file.txt"));
+        let binary = format!("{} *data.bin\r", hex);
+        assert_eq!(parse_check_line(binary.as_bytes()), entry(b"data.bin"));
+        assert_eq!(parse_check_line(format!("{}  a", hex.to_uppercase()).as_bytes()), entry(b"a"));
+
+        // Paths are raw bytes - invalid UTF-8 is kept as-is
+        let mut raw = format!("{}  ", hex).into_bytes();
+        raw.extend_from_slice(b"caf\xe9");
+        assert_eq!(parse_check_line(&raw), entry(b"caf\xe9"));
+
+        // A leading backslash means the path is escaped; without it, backslashes are literal
+        let escaped = format!("\\{}  two\\nlines\\\\x\\r", hex);
+        assert_eq!(parse_check_line(escaped.as_bytes()), entry(b"two\nlines\\x\r"));
+        assert_eq!(parse_check_line(format!("{}  a\\nb", hex).as_bytes()), entry(b"a\\nb"));
+        assert_eq!(parse_check_line(format!("\\{}  a\\tb", hex).as_bytes()), CheckLine::Malformed);
+        assert_eq!(parse_check_line(format!("\\{}  a\\", hex).as_bytes()), CheckLine::Malformed);
+
+        assert_eq!(parse_check_line(b""), CheckLine::Skip);
+        assert_eq!(parse_check_line(b"   "), CheckLine::Skip);
+        assert_eq!(parse_check_line(b"# generated by blitzsum"), CheckLine::Skip);
+
+        assert_eq!(parse_check_line(format!("{} x", hex).as_bytes()), CheckLine::Malformed);
+        assert_eq!(parse_check_line(format!("{}  ", hex).as_bytes()), CheckLine::Malformed);
+        assert_eq!(parse_check_line(format!("{}g  a", &hex[..63]).as_bytes()), CheckLine::Malformed);
+        assert_eq!(parse_check_line(format!("{}  a", "zz".repeat(32)).as_bytes()), CheckLine::Malformed);
+    }
+
+    #[test]
+    fn test_run_check_reports_each_file() {
+        let dir = std::env::temp_dir().join(format!("blitzsum-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let spaced = dir.join("name with spaces.txt");
+        let changed = dir.join("changed.txt");
+        std::fs::write(&spaced, b"hello").unwrap();
+        std::fs::write(&changed, b"after").unwrap();
+        let missing = dir.join("missing.txt");
+
+        let contents = format!(
+            "# checksums\n{}  {}\n{} *{}\n\n{}  {}\nnot a checksum line\n",
+            blitzhash::blitz_hash_hex(7, b"hello"),
+            spaced.display(),
+            blitzhash::blitz_hash_hex(7, b"before"),
+            changed.display(),
+            "00".repeat(32),
+            missing.display(),
+        );
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let summary = run_check(7, Path::new("SUMS"), contents.as_bytes(), &mut out, &mut err).unwrap();
+        let (out, err) = (String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(out.contains(&format!("{}: OK\n", spaced.display())), "{}", out);
+        assert!(out.contains(&format!("{}: FAILED\n", changed.display())), "{}", out);
+        assert!(out.contains(&format!("{}: FAILED: no such file\n", missing.display())), "{}", out);
+        assert_eq!(err, "blitzsum: SUMS:6: improperly formatted checksum line\n");
+        assert!(!out.contains("improperly formatted checksum line"), "{}", out);
+        assert_eq!(summary, CheckSummary { ok: 1, failed: 1, unreadable: 1, malformed: 1 });
+        assert_eq!(summary.exit_code(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_escaped_paths_round_trip() {
+        let dir = std::env::temp_dir().join(format!("blitzsum-escape-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let names = ["two\nlines", "back\\slash", "plain"];
+        let files: Vec<PathBuf> = names
+            .iter()
+            .map(|name| {
+                let path = dir.join(name);
+                std::fs::write(&path, name).unwrap();
+                path
+            })
+            .collect();
+
+        let mut sums = Vec::new();
+        assert_eq!(run_generate(5, &files, &mut sums).unwrap(), 0);
+        assert_eq!(sums.iter().filter(|&&b| b == b'\n').count(), names.len());
+        assert!(sums.starts_with(b"\\"));
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let summary = run_check(5, Path::new("SUMS"), &sums, &mut out, &mut err).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(summary, CheckSummary { ok: 3, ..Default::default() }, "{}", String::from_utf8_lossy(&out));
+        assert!(err.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_path_round_trip() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = std::env::temp_dir().join(format!("blitzsum-non-utf8-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(std::ffi::OsStr::from_bytes(b"caf\xe9"));
+        std::fs::write(&path, b"latin-1 name").unwrap();
+
+        let mut sums = Vec::new();
+        assert_eq!(run_generate(9, std::slice::from_ref(&path), &mut sums).unwrap(), 0);
+        assert!(sums.ends_with(b"caf\xe9\n"));
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let summary = run_check(9, Path::new("SUMS"), &sums, &mut out, &mut err).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(summary, CheckSummary { ok: 1, ..Default::default() }, "{}", String::from_utf8_lossy(&out));
+        assert!(out.starts_with(path.as_os_str().as_bytes()));
+        assert!(err.is_empty());
+    }
+
+    #[test]
+    fn test_check_exit_code() {
+        assert_eq!(CheckSummary { ok: 2, ..Default::default() }.exit_code(), 0);
+        assert_eq!(CheckSummary::default().exit_code(), 1);
+        assert_eq!(CheckSummary { ok: 2, malformed: 1, ..Default::default() }.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_hash_reader_matches_blitz_hash() {
+        let data = vec![0x42u8; 200_000];
+        assert_eq!(hash_reader(3, &data[..]).unwrap(), blitzhash::blitz_hash(3, &data));
+    }
+}
@@ -1,11 +1,136 @@
 //! BlitzHash - HIGH PERFORMANCE (Actually Fast Edition)
 //! **WARNING: NOT CRYPTOGRAPHICALLY SECURE**
 
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
+
+use std::fmt;
+use std::io::{self, IoSlice, Read};
+use std::str::FromStr;
+
+/// Log the four lanes at one step to stderr (`trace` feature) - compiles to nothing otherwise
+macro_rules! trace_lanes {
+    ($stage:expr, $lanes:expr) => {
+        #[cfg(feature = "trace")]
+        {
+            let lanes: [u64; 4] = $lanes;
+            eprintln!(
+                "[blitzhash] {:<22} {:016x} {:016x} {:016x} {:016x}",
+                $stage, lanes[0], lanes[1], lanes[2], lanes[3]
+            );
+        }
+    };
+}
+
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "serde")]
+pub use serialize::blitz_hash_serde;
+#[cfg(feature = "portable-simd")]
+mod simd;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "stats")]
+pub use stats::estimate_bias;
+
 const K1: u64 = 0x517cc1b727220a95;
 const K2: u64 = 0x85ebca6b2f3c8b51;
 const K3: u64 = 0xc2b2ae3d27d4eb4f;
 const K4: u64 = 0x165667b19e3779f9;
 
+/// Low-level building blocks for analysis harnesses and custom combiners
+///
+/// **No stability guarantee** - these may change whenever the algorithm does.
+///
+/// One 32-byte block of `blitz_hash`, rebuilt by hand:
+///
+/// ```
+/// use blitzhash::blitz_hash;
+/// use blitzhash::primitives::{avalanche, mix, K1, K2, K3, K4};
+///
+/// let seed = 9u64;
+/// let block = *b"0123456789abcdefghijklmnopqrstuv";
+/// let word = |i: usize| u64::from_le_bytes(block[i * 8..i * 8 + 8].try_into().unwrap());
+///
+/// let mut state = [
+///     mix(seed ^ K1, word(0), K1),
+///     mix(seed ^ K2, word(1), K2),
+///     mix(seed ^ K3, word(2), K3),
+///     mix(seed ^ K4, word(3), K4),
+/// ];
+///
+/// // Length mixing, then the avalanche
+/// let len = 32u64;
+/// state[0] ^= len;
+/// state[1] ^= len.rotate_right(17);
+/// state[2] ^= len.rotate_right(31);
+/// state[3] ^= len.rotate_right(47);
+/// avalanche(&mut state);
+///
+/// let digest = blitz_hash(seed, &block);
+/// for (i, lane) in state.iter().enumerate() {
+///     assert_eq!(&digest[i * 8..i * 8 + 8], &lane.to_le_bytes());
+/// }
+/// ```
+pub mod primitives {
+    /// Lane 0 multiplier (also the second multiplier inside `mix`)
+    pub const K1: u64 = super::K1;
+    /// Lane 1 multiplier
+    pub const K2: u64 = super::K2;
+    /// Lane 2 multiplier
+    pub const K3: u64 = super::K3;
+    /// Lane 3 multiplier
+    pub const K4: u64 = super::K4;
+
+    /// Fold one 64-bit word into one lane with lane multiplier `k`
+    #[inline(always)]
+    pub fn mix(state: u64, chunk: u64, k: u64) -> u64 {
+        super::mix_chunk(state, chunk, k)
+    }
+
+    /// Final avalanche over all four lanes, exactly as every finalizer applies it
+    #[inline(always)]
+    pub fn avalanche(state: &mut [u64; 4]) {
+        super::avalanche(state);
+    }
+}
+
+/// Errors from the fallible BlitzHash APIs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlitzError {
+    /// Output slice can't hold the 32-byte digest
+    OutputTooSmall { len: usize },
+    /// Text isn't 64 hex characters
+    InvalidHex,
+    /// `blitz_hash_serde` couldn't encode the value
+    Unserializable,
+    /// `blitz_hash_truncated` length outside 1..=32
+    InvalidOutputLength { len: usize },
+}
+
+impl fmt::Display for BlitzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlitzError::OutputTooSmall { len } => {
+                write!(f, "output slice holds {} bytes, digest needs 32", len)
+            }
+            BlitzError::InvalidHex => write!(f, "digest must be 64 hex characters"),
+            BlitzError::Unserializable => {
+                write!(f, "value can't be hashed: unknown-length sequence or Serialize error")
+            }
+            BlitzError::InvalidOutputLength { len } => {
+                write!(f, "output length must be 1..=32, got {}", len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BlitzError {}
+
 /// Fast unaligned u64 read - NO BOUNDS CHECKS
 #[inline(always)]
 unsafe fn read_u64_unaligned(ptr: *const u8) -> u64 {
@@ -23,20 +148,377 @@ fn mix_chunk(mut h: u64, chunk: u64, k: u64) -> u64 {
     h
 }
 
+/// Final avalanche - AGGRESSIVE (4 rounds for better diffusion)
+///
+/// Zero is a fixed point of multiply-xor-rotate, so every lane is first XORed
+/// with `AVALANCHE_SALT`. That is a bijection: an all-zero state no longer stays
+/// zero, and distinct states still reach the rounds as distinct states.
+#[inline(always)]
+fn avalanche(state: &mut [u64; 4]) {
+    avalanche_rounds(state, AVALANCHE_ROUNDS);
+}
+
+/// Avalanche rounds - 3 with the `legacy-3-rounds` feature (changes ALL digests)
+const AVALANCHE_ROUNDS: usize = if cfg!(feature = "legacy-3-rounds") { 3 } else { 4 };
+
+/// Pre-avalanche lane salt (golden ratio) - 0 with `legacy-3-rounds`, which
+/// reproduces the older finalize exactly, zero fixed point included
+const AVALANCHE_SALT: u64 = if cfg!(feature = "legacy-3-rounds") { 0 } else { 0x9E3779B97F4A7C15 };
+
+#[inline(always)]
+fn avalanche_rounds(state: &mut [u64; 4], rounds: usize) {
+    for lane in state.iter_mut() {
+        *lane ^= AVALANCHE_SALT;
+    }
+
+    for _ in 0..rounds {
+        state[0] = state[0].wrapping_mul(K1) ^ state[0].rotate_right(29);
+        state[1] = state[1].wrapping_mul(K2) ^ state[1].rotate_right(31);
+        state[2] = state[2].wrapping_mul(K3) ^ state[2].rotate_right(33);
+        state[3] = state[3].wrapping_mul(K4) ^ state[3].rotate_right(37);
+    }
+}
+
 /// Ultra-fast baseline hash - FIXED
+///
+/// ```
+/// use blitzhash::blitz_hash;
+///
+/// let digest = blitz_hash(0, b"Hello, world!");
+/// # #[cfg(not(feature = "legacy-3-rounds"))]
+/// assert_eq!(hex::encode(&digest[..8]), "6ad182d13ceab0b7");
+/// assert_ne!(digest, blitz_hash(1, b"Hello, world!"));
+/// ```
 pub fn blitz_hash(seed: u64, data: &[u8]) -> [u8; 32] {
-    let mut state = [seed ^ K1, seed ^ K2, seed ^ K3, seed ^ K4];
+    blitz_hash_keyed256([seed; 4], data)
+}
+
+/// 64-bit hash - the four digest words of `blitz_hash` XOR-folded together
+pub fn blitz_hash64(seed: u64, data: &[u8]) -> u64 {
+    fold64(&blitz_hash(seed, data))
+}
+
+/// XOR-fold of the four digest words - the one 64-bit reduction every API uses
+#[inline]
+fn fold64(digest: &[u8; 32]) -> u64 {
+    let words = digest_to_words(digest);
+    words[0] ^ words[1] ^ words[2] ^ words[3]
+}
+
+/// First `out_len` bytes (1..=32) of a re-mixed digest - not a prefix of `blitz_hash`
+///
+/// For `out_len < 32` each digest word is keyed by `out_len` on its own (no
+/// cross-folding, so no digest bits are lost) and given one more avalanche round
+/// before truncating. Outputs of different lengths for the same input are therefore
+/// unrelated rather than prefixes of each other. `out_len == 32` is exactly `blitz_hash`.
+///
+/// Errors if `out_len` is 0 or above 32.
+pub fn blitz_hash_truncated(seed: u64, data: &[u8], out_len: usize) -> Result<Vec<u8>, BlitzError> {
+    if !(1..=32).contains(&out_len) {
+        return Err(BlitzError::InvalidOutputLength { len: out_len });
+    }
+    let digest = blitz_hash(seed, data);
+    if out_len == 32 {
+        return Ok(digest.to_vec());
+    }
+
+    let key = out_len as u64;
+    let words = digest_to_words(&digest);
+    let mut state = [
+        words[0] ^ key,
+        words[1] ^ key.rotate_right(17),
+        words[2] ^ key.rotate_right(31),
+        words[3] ^ key.rotate_right(47),
+    ];
+    avalanche_rounds(&mut state, 1);
+    Ok(words_to_digest(&state)[..out_len].to_vec())
+}
+
+/// `blitz_hash` varied by a per-message `nonce`, mixed in just before the avalanche
+///
+/// `seed` picks the hash family, `nonce` the member for one message. Every nonce,
+/// 0 included, gives digests unrelated to plain `blitz_hash`.
+pub fn blitz_hash_nonce(seed: u64, nonce: u64, data: &[u8]) -> [u8; 32] {
+    let state = [seed ^ K1, seed ^ K2, seed ^ K3, seed ^ K4];
+    finish_nonce(fold_input(state, data), data.len() as u64, nonce)
+}
+
+/// `blitz_hash` as 64 lowercase hex characters
+pub fn blitz_hash_hex(seed: u64, data: &[u8]) -> String {
+    digest_hex(&blitz_hash(seed, data))
+}
+
+/// Lowercase hex of a digest - one fixed-size pass, no per-byte formatting
+fn digest_hex(digest: &[u8; 32]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = [0u8; 64];
+    for (pair, byte) in out.chunks_exact_mut(2).zip(digest) {
+        pair[0] = DIGITS[(byte >> 4) as usize];
+        pair[1] = DIGITS[(byte & 0xf) as usize];
+    }
+    // Only ASCII digits were written
+    String::from_utf8(out.to_vec()).unwrap()
+}
+
+/// `blitz_hash` plus the number of bytes it consumed (always `data.len()`)
+pub fn blitz_hash_counted(seed: u64, data: &[u8]) -> ([u8; 32], usize) {
+    (blitz_hash(seed, data), data.len())
+}
+
+/// Byte order of the four digest words in the 32-byte output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endian {
+    /// `to_le_bytes` per word - the canonical output of every other API
+    #[default]
+    Little,
+    /// `to_be_bytes` per word, for systems expecting big-endian words
+    Big,
+}
+
+impl Endian {
+    /// Re-serialize a canonical (little-endian) digest in this word order
+    #[inline]
+    fn apply(self, mut digest: [u8; 32]) -> [u8; 32] {
+        if self == Endian::Big {
+            for word in digest.chunks_exact_mut(8) {
+                word.reverse();
+            }
+        }
+        digest
+    }
+}
+
+/// `blitz_hash` with a chosen word byte order - `Endian::Little` is `blitz_hash`
+pub fn blitz_hash_with_endian(seed: u64, data: &[u8], endian: Endian) -> [u8; 32] {
+    endian.apply(blitz_hash(seed, data))
+}
+
+/// Speed/quality preset for `blitz_hash_profile`
+///
+/// The profiles are different hash functions: a digest from one never matches the
+/// same input under another, so pick one per use and persist which one it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlitzProfile {
+    /// 2 avalanche rounds; the sub-32-byte remainder goes in as one zero-padded block
+    /// with no cross-word rotation
+    Fast,
+    /// Exactly `blitz_hash`
+    #[default]
+    Balanced,
+    /// 8 avalanche rounds plus a second, rotated pass over the last 32 bytes
+    Strong,
+}
+
+/// `blitz_hash` at a chosen point on the speed/quality curve - see `BlitzProfile`
+pub fn blitz_hash_profile(profile: BlitzProfile, seed: u64, data: &[u8]) -> [u8; 32] {
+    let state = [seed ^ K1, seed ^ K2, seed ^ K3, seed ^ K4];
+    match profile {
+        BlitzProfile::Fast => {
+            let mut state = state;
+            let pos = fold_blocks(&mut state, data);
+            if pos < data.len() {
+                let mut block = [0u8; 32];
+                block[..data.len() - pos].copy_from_slice(&data[pos..]);
+                fold_blocks(&mut state, &block);
+            }
+            finish_rounds(state, data.len() as u64, 2)
+        }
+        BlitzProfile::Balanced => blitz_hash(seed, data),
+        BlitzProfile::Strong => {
+            let mut state = fold_input(state, data);
+            let mut last = [0u8; 32];
+            let start = data.len().saturating_sub(32);
+            last[..data.len() - start].copy_from_slice(&data[start..]);
+            for word in last.chunks_exact(8) {
+                mix_tail(&mut state, u64::from_le_bytes(word.try_into().unwrap()));
+            }
+            finish_rounds(state, data.len() as u64, 8)
+        }
+    }
+}
+
+/// SimHash fingerprint for near-duplicate detection
+///
+/// Every `shingle`-byte window is hashed with `blitz_hash64` and votes on each of
+/// the 64 output bits, so similar inputs get fingerprints at small Hamming distance
+/// and unrelated inputs land ~32 bits apart. Inputs no longer than `shingle` are a
+/// single shingle; `shingle == 0` is treated as 1.
+pub fn blitz_simhash(seed: u64, data: &[u8], shingle: usize) -> u64 {
+    if data.is_empty() {
+        return blitz_hash64(seed, data);
+    }
+
+    let shingle = shingle.clamp(1, data.len());
+    let mut votes = [0i64; 64];
+    for window in data.windows(shingle) {
+        let h = blitz_hash64(seed, window);
+        for (bit, vote) in votes.iter_mut().enumerate() {
+            *vote += if h >> bit & 1 == 1 { 1 } else { -1 };
+        }
+    }
+
+    votes
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (bit, &vote)| if vote > 0 { acc | 1 << bit } else { acc })
+}
+
+/// One-shot hash with a 128-bit seed
+///
+/// The key is `[lo, lo, hi, hi]`. `key_state` mixes `lo ^ hi` into every lane, so any
+/// seed bit moves the whole digest. A `u64` seed is that seed repeated -
+/// `blitz_hash_seed128(seed128_from_u64(s), d) == blitz_hash(s, d)`.
+pub fn blitz_hash_seed128(seed: [u8; 16], data: &[u8]) -> [u8; 32] {
+    blitz_hash_keyed256(seed128_key(seed), data)
+}
+
+/// The 128-bit seed equivalent to a `u64` seed
+pub fn seed128_from_u64(seed: u64) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    out[..8].copy_from_slice(&seed.to_le_bytes());
+    out[8..].copy_from_slice(&seed.to_le_bytes());
+    out
+}
+
+#[inline]
+fn seed128_key(seed: [u8; 16]) -> [u64; 4] {
+    let lo = u64::from_le_bytes(seed[..8].try_into().unwrap());
+    let hi = u64::from_le_bytes(seed[8..].try_into().unwrap());
+    [lo, lo, hi, hi]
+}
+
+/// One-shot hash keyed with a full 256-bit key
+///
+/// Lane `i` starts at `key[i] ^ K_i`, plus mixes of XORs of the key words - see
+/// `key_state`. Those XORs are zero for `[seed; 4]`, so `blitz_hash(seed, data)` is
+/// `blitz_hash_keyed256([seed; 4], data)`, while a change to any one key word moves
+/// all four output words.
+pub fn blitz_hash_keyed256(key: [u64; 4], data: &[u8]) -> [u8; 32] {
+    let state = key_state(key);
+    if data.len() <= 16 {
+        return hash_small(state, data);
+    }
+    hash_lanes(state, 0, data)
+}
+
+/// Initial lanes for a 256-bit key
+///
+/// The lanes run independently until the end, so the key is cross-mixed here in
+/// three XOR steps, each undoable on its own so distinct keys keep distinct states:
+/// every lane gets a mix of all four words XORed (the four copies cancel out of that
+/// XOR), lanes 1 and 3 a mix of lanes 0 ^ 2, then lanes 0 and 2 a mix of lanes 1 ^ 3
+/// (two copies cancel). A mix of 0 is 0, so `[seed; 4]` keys get nothing added.
+#[inline(always)]
+fn key_state(key: [u64; 4]) -> [u64; 4] {
+    let mut lanes = key;
+    let spread = mix_chunk(0, lanes[0] ^ lanes[1] ^ lanes[2] ^ lanes[3], K1);
+    for lane in lanes.iter_mut() {
+        *lane ^= spread;
+    }
+    let spread = mix_chunk(0, lanes[0] ^ lanes[2], K2);
+    lanes[1] ^= spread;
+    lanes[3] ^= spread;
+    let spread = mix_chunk(0, lanes[1] ^ lanes[3], K3);
+    lanes[0] ^= spread;
+    lanes[2] ^= spread;
+    [lanes[0] ^ K1, lanes[1] ^ K2, lanes[2] ^ K3, lanes[3] ^ K4]
+}
+
+/// Small-key specialization (<= 16 bytes) - same digest as `hash_lanes`
+///
+/// Skips the block loop and the tail copy: at most two word mixes from overlapping
+/// loads, then the full `finish`. It does not shorten the avalanche, which would
+/// change every short-key digest.
+#[inline(always)]
+fn hash_small(mut state: [u64; 4], data: &[u8]) -> [u8; 32] {
+    let len = data.len();
+    debug_assert!(len <= 16);
+    if len >= 8 {
+        mix_word(&mut state, read_small(&data[..8]));
+        if len == 16 {
+            mix_word(&mut state, read_small(&data[8..]));
+        } else if len > 8 {
+            mix_tail(&mut state, read_small(&data[8..]));
+        }
+    } else if len > 0 {
+        mix_tail(&mut state, read_small(data));
+    }
+
+    finish(state, len as u64)
+}
+
+/// Zero-padded little-endian u64 of up to 8 bytes
+///
+/// Overlapping loads put shared bytes at the same bit position, so OR-ing them
+/// gives exactly the padded value without a byte-by-byte copy.
+#[inline(always)]
+fn read_small(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    debug_assert!(len <= 8);
+    if len == 8 {
+        u64::from_le_bytes(bytes.try_into().unwrap())
+    } else if len >= 4 {
+        let lo = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as u64;
+        let hi = u32::from_le_bytes(bytes[len - 4..].try_into().unwrap()) as u64;
+        lo | hi << ((len - 4) * 8)
+    } else if len > 0 {
+        let first = bytes[0] as u64;
+        let mid = (bytes[len / 2] as u64) << (len / 2 * 8);
+        let last = (bytes[len - 1] as u64) << ((len - 1) * 8);
+        first | mid | last
+    } else {
+        0
+    }
+}
+
+/// One-shot core - `state` has already absorbed `prior_len` bytes in whole 32-byte blocks
+#[inline(always)]
+fn hash_lanes(state: [u64; 4], prior_len: u64, data: &[u8]) -> [u8; 32] {
+    finish(fold_input(state, data), prior_len + data.len() as u64)
+}
+
+/// Blocks, trailing words and zero-padded tail into the lanes - everything but `finish`
+#[inline(always)]
+fn fold_input(mut state: [u64; 4], data: &[u8]) -> [u64; 4] {
+    let mut pos = fold_blocks(&mut state, data);
+    
+    // Process remaining 8-byte chunks
+    while has_remaining(data.len(), pos, 8) {
+        mix_word(&mut state, unsafe { read_u64_unaligned(data.as_ptr().add(pos)) });
+        pos += 8;
+    }
+    
+    // Tail handling - DISTRIBUTE ACROSS ALL LANES
+    if pos < data.len() {
+        let mut tail = [0u8; 8];
+        let rem = data.len() - pos;
+        tail[..rem].copy_from_slice(&data[pos..]);
+        mix_tail(&mut state, u64::from_le_bytes(tail));
+    }
+    
+    state
+}
+
+#[cfg(feature = "portable-simd")]
+use simd::fold_blocks;
+#[cfg(not(feature = "portable-simd"))]
+use fold_blocks_scalar as fold_blocks;
+
+/// Whole 32-byte blocks, word `i` into lane `i` - returns the offset of the remainder
+#[inline(always)]
+#[cfg_attr(all(feature = "portable-simd", not(test)), allow(dead_code))]
+fn fold_blocks_scalar(state: &mut [u64; 4], data: &[u8]) -> usize {
     let mut pos = 0;
     
     // Process 32-byte chunks (4×8) - UNROLLED with proper reads
-    while pos + 32 <= data.len() {
+    while has_remaining(data.len(), pos, 32) {
         unsafe {
             // Prefetch next cache line
             #[cfg(target_arch = "x86_64")]
             {
                 use std::arch::x86_64::_mm_prefetch;
                 const _MM_HINT_T0: i32 = 3;
-                if pos + 64 <= data.len() {
+                if has_remaining(data.len(), pos, 64) {
                     _mm_prefetch(data.as_ptr().add(pos + 64) as *const i8, _MM_HINT_T0);
                 }
             }
@@ -47,239 +529,1057 @@ pub fn blitz_hash(seed: u64, data: &[u8]) -> [u8; 32] {
             let c2 = read_u64_unaligned(ptr.add(16));
             let c3 = read_u64_unaligned(ptr.add(24));
             
+            trace_lanes!(format!("block @{} in", pos), *state);
             state[0] = mix_chunk(state[0], c0, K1);
             state[1] = mix_chunk(state[1], c1, K2);
             state[2] = mix_chunk(state[2], c2, K3);
             state[3] = mix_chunk(state[3], c3, K4);
+            trace_lanes!(format!("block @{} out", pos), *state);
         }
         
         pos += 32;
     }
     
-    // Process remaining 8-byte chunks
-    while pos + 8 <= data.len() {
-        unsafe {
-            let chunk = read_u64_unaligned(data.as_ptr().add(pos));
-            state[0] = mix_chunk(state[0], chunk, K1);
-            state[1] = mix_chunk(state[1], chunk.rotate_left(11), K2);
-            state[2] = mix_chunk(state[2], chunk.rotate_left(23), K3);
-            state[3] = mix_chunk(state[3], chunk.rotate_left(37), K4);
-        }
-        pos += 8;
-    }
-    
-    // Tail handling - DISTRIBUTE ACROSS ALL LANES
-    if pos < data.len() {
-        let mut tail = [0u8; 8];
-        let rem = data.len() - pos;
-        tail[..rem].copy_from_slice(&data[pos..]);
-        let chunk = u64::from_le_bytes(tail);
-        
-        // Mix tail into ALL lanes with rotation for diffusion
-        state[0] = mix_chunk(state[0], chunk, K1);
-        state[1] = mix_chunk(state[1], chunk.rotate_left(13), K2);
-        state[2] = mix_chunk(state[2], chunk.rotate_left(27), K3);
-        state[3] = mix_chunk(state[3], chunk.rotate_left(43), K4);
-    }
-    
-    // Length mixing
-    let len = data.len() as u64;
+    pos
+}
+
+/// At least `n` bytes left after `pos` - never overflows, unlike `pos + n <= len`
+///
+/// Callers keep `pos <= len`, so the subtraction can't underflow either; this holds
+/// even for slices whose length is near `usize::MAX` (e.g. huge sparse mmaps).
+#[inline(always)]
+fn has_remaining(len: usize, pos: usize, n: usize) -> bool {
+    len - pos >= n
+}
+
+/// Trailing 8-byte word into ALL lanes
+#[inline(always)]
+fn mix_word(state: &mut [u64; 4], chunk: u64) {
+    trace_lanes!(format!("word {:016x} in", chunk), *state);
+    state[0] = mix_chunk(state[0], chunk, K1);
+    state[1] = mix_chunk(state[1], chunk.rotate_left(11), K2);
+    state[2] = mix_chunk(state[2], chunk.rotate_left(23), K3);
+    state[3] = mix_chunk(state[3], chunk.rotate_left(37), K4);
+    trace_lanes!("word out", *state);
+}
+
+/// Zero-padded final 1..=7 bytes into ALL lanes with rotation for diffusion
+#[inline(always)]
+fn mix_tail(state: &mut [u64; 4], chunk: u64) {
+    trace_lanes!(format!("tail {:016x} in", chunk), *state);
+    state[0] = mix_chunk(state[0], chunk, K1);
+    state[1] = mix_chunk(state[1], chunk.rotate_left(13), K2);
+    state[2] = mix_chunk(state[2], chunk.rotate_left(27), K3);
+    state[3] = mix_chunk(state[3], chunk.rotate_left(43), K4);
+    trace_lanes!("tail out", *state);
+}
+
+/// Length mixing, avalanche, little-endian output - shared by every finalizer
+#[inline(always)]
+fn finish(state: [u64; 4], len: u64) -> [u8; 32] {
+    finish_rounds(state, len, AVALANCHE_ROUNDS)
+}
+
+/// `finish` with a chosen avalanche round count - only `BlitzProfile` varies it
+#[inline(always)]
+fn finish_rounds(mut state: [u64; 4], len: u64, rounds: usize) -> [u8; 32] {
+    mix_len(&mut state, len);
+    avalanche_rounds(&mut state, rounds);
+    trace_lanes!("avalanched", state);
+    words_to_digest(&state)
+}
+
+/// `finish` with a per-message nonce folded into every lane just before the avalanche
+#[inline(always)]
+fn finish_nonce(mut state: [u64; 4], len: u64, nonce: u64) -> [u8; 32] {
+    mix_len(&mut state, len);
+    state[0] = mix_chunk(state[0], nonce, K1);
+    state[1] = mix_chunk(state[1], nonce.rotate_right(17), K2);
+    state[2] = mix_chunk(state[2], nonce.rotate_right(31), K3);
+    state[3] = mix_chunk(state[3], nonce.rotate_right(47), K4);
+    trace_lanes!(format!("nonce {:016x} mixed", nonce), state);
+
+    avalanche(&mut state);
+    trace_lanes!("avalanched", state);
+    words_to_digest(&state)
+}
+
+#[inline(always)]
+fn mix_len(state: &mut [u64; 4], len: u64) {
     state[0] ^= len;
     state[1] ^= len.rotate_right(17);
     state[2] ^= len.rotate_right(31);
     state[3] ^= len.rotate_right(47);
-    
-    // Final avalanche - AGGRESSIVE (4 rounds for better diffusion)
-    for _ in 0..4 {
-        state[0] = state[0].wrapping_mul(K1) ^ state[0].rotate_right(29);
-        state[1] = state[1].wrapping_mul(K2) ^ state[1].rotate_right(31);
-        state[2] = state[2].wrapping_mul(K3) ^ state[2].rotate_right(33);
-        state[3] = state[3].wrapping_mul(K4) ^ state[3].rotate_right(37);
-    }
-    
+    trace_lanes!(format!("len {} mixed", len), *state);
+}
+
+/// Digest layout: word `i` is little-endian at bytes `8 * i..8 * i + 8`
+#[inline(always)]
+pub fn words_to_digest(words: &[u64; 4]) -> [u8; 32] {
     let mut output = [0u8; 32];
-    output[0..8].copy_from_slice(&state[0].to_le_bytes());
-    output[8..16].copy_from_slice(&state[1].to_le_bytes());
-    output[16..24].copy_from_slice(&state[2].to_le_bytes());
-    output[24..32].copy_from_slice(&state[3].to_le_bytes());
+    output[0..8].copy_from_slice(&words[0].to_le_bytes());
+    output[8..16].copy_from_slice(&words[1].to_le_bytes());
+    output[16..24].copy_from_slice(&words[2].to_le_bytes());
+    output[24..32].copy_from_slice(&words[3].to_le_bytes());
     output
 }
 
-/// Streaming API (kept for compatibility)
-#[derive(Clone)]
-pub struct BlitzState {
-    state: [u64; 4],
-    buffer: [u8; 8],
-    buffer_len: usize,
-    total_len: u64,
+/// Inverse of `words_to_digest`
+#[inline]
+pub fn digest_to_words(digest: &[u8; 32]) -> [u64; 4] {
+    [
+        u64::from_le_bytes(digest[0..8].try_into().unwrap()),
+        u64::from_le_bytes(digest[8..16].try_into().unwrap()),
+        u64::from_le_bytes(digest[16..24].try_into().unwrap()),
+        u64::from_le_bytes(digest[24..32].try_into().unwrap()),
+    ]
 }
 
-impl BlitzState {
-    pub fn new(seed: u64) -> Self {
-        Self {
-            state: [seed ^ K1, seed ^ K2, seed ^ K3, seed ^ K4],
-            buffer: [0u8; 8],
-            buffer_len: 0,
-            total_len: 0,
-        }
-    }
-
-    pub fn absorb(&mut self, data: &[u8]) {
-        let mut pos = 0;
-        self.total_len += data.len() as u64;
+/// Domain-separated hash - same seed, independent digests per `domain`
+///
+/// Absorbs `domain.len()` as a little-endian u64, then `domain`, then `data`, so
+/// `(b"ab", b"c")` and `(b"a", b"bc")` never collide by concatenation.
+pub fn blitz_hash_domain(domain: &[u8], seed: u64, data: &[u8]) -> [u8; 32] {
+    let mut state = BlitzState::new(seed);
+    state.absorb_framed(domain);
+    state.absorb(data);
+    state.finalize()
+}
 
-        // Handle buffered bytes first
-        if self.buffer_len > 0 {
-            let needed = 8 - self.buffer_len;
-            let available = data.len().min(needed);
-            self.buffer[self.buffer_len..self.buffer_len + available]
-                .copy_from_slice(&data[..available]);
-            self.buffer_len += available;
-            pos += available;
+/// Hash a composite key field by field - boundaries are part of the digest
+///
+/// `[b"ab", b"c"]` and `[b"a", b"bc"]` hash differently, unlike plain concatenation.
+pub fn blitz_hash_fields(seed: u64, fields: &[&[u8]]) -> [u8; 32] {
+    let mut state = BlitzState::new(seed);
+    for field in fields {
+        state.absorb_framed(field);
+    }
+    state.finalize()
+}
 
-            if self.buffer_len == 8 {
-                let chunk = u64::from_le_bytes(self.buffer);
-                // Mix into ALL lanes consistently
-                self.state[0] = mix_chunk(self.state[0], chunk, K1);
-                self.state[1] = mix_chunk(self.state[1], chunk.rotate_left(11), K2);
-                self.state[2] = mix_chunk(self.state[2], chunk.rotate_left(23), K3);
-                self.state[3] = mix_chunk(self.state[3], chunk.rotate_left(37), K4);
-                self.buffer_len = 0;
-            }
-        }
+/// Hash scatter/gather buffers as their logical concatenation - no gathering copy
+pub fn blitz_hash_vectored(seed: u64, bufs: &[IoSlice<'_>]) -> [u8; 32] {
+    let mut state = BlitzState::new(seed);
+    for buf in bufs {
+        state.absorb(buf);
+    }
+    state.finalize()
+}
 
-        // Process 8-byte chunks
-        while pos + 8 <= data.len() {
-            unsafe {
-                let chunk = read_u64_unaligned(data.as_ptr().add(pos));
-                self.state[0] = mix_chunk(self.state[0], chunk, K1);
-                self.state[1] = mix_chunk(self.state[1], chunk.rotate_left(11), K2);
-                self.state[2] = mix_chunk(self.state[2], chunk.rotate_left(23), K3);
-                self.state[3] = mix_chunk(self.state[3], chunk.rotate_left(37), K4);
-            }
-            pos += 8;
-        }
+/// A finished digest - read it, format it or check it, but never absorb into it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinalizedBlitz([u8; 32]);
 
-        // Buffer remaining bytes
-        if pos < data.len() {
-            let remaining = data.len() - pos;
-            self.buffer[..remaining].copy_from_slice(&data[pos..]);
-            self.buffer_len = remaining;
-        }
+impl FinalizedBlitz {
+    pub fn bytes(&self) -> &[u8; 32] {
+        &self.0
     }
 
-    pub fn finalize(mut self) -> [u8; 32] {
-        // Process remaining buffered bytes
-        if self.buffer_len > 0 {
-            for i in self.buffer_len..8 {
-                self.buffer[i] = 0;
-            }
-            let chunk = u64::from_le_bytes(self.buffer);
-            // Mix into ALL lanes
-            self.state[0] = mix_chunk(self.state[0], chunk, K1);
-            self.state[1] = mix_chunk(self.state[1], chunk.rotate_left(13), K2);
-            self.state[2] = mix_chunk(self.state[2], chunk.rotate_left(27), K3);
-            self.state[3] = mix_chunk(self.state[3], chunk.rotate_left(43), K4);
-        }
-
-        // Mix in total length
-        let len = self.total_len;
-        self.state[0] ^= len;
-        self.state[1] ^= len.rotate_right(17);
-        self.state[2] ^= len.rotate_right(31);
-        self.state[3] ^= len.rotate_right(47);
+    /// Lowercase 64-char hex
+    pub fn hex(&self) -> String {
+        digest_hex(&self.0)
+    }
 
-        // Final avalanche
-        for _ in 0..4 {
-            self.state[0] = self.state[0].wrapping_mul(K1) ^ self.state[0].rotate_right(29);
-            self.state[1] = self.state[1].wrapping_mul(K2) ^ self.state[1].rotate_right(31);
-            self.state[2] = self.state[2].wrapping_mul(K3) ^ self.state[2].rotate_right(33);
-            self.state[3] = self.state[3].wrapping_mul(K4) ^ self.state[3].rotate_right(37);
-        }
+    /// True if `expected` is exactly this digest (wrong-length input never matches)
+    pub fn verify(&self, expected: &[u8]) -> bool {
+        expected == self.0
+    }
+}
 
-        let mut output = [0u8; 32];
-        output[0..8].copy_from_slice(&self.state[0].to_le_bytes());
-        output[8..16].copy_from_slice(&self.state[1].to_le_bytes());
-        output[16..24].copy_from_slice(&self.state[2].to_le_bytes());
-        output[24..32].copy_from_slice(&self.state[3].to_le_bytes());
-        output
+impl From<BlitzState> for FinalizedBlitz {
+    fn from(state: BlitzState) -> Self {
+        state.into_finalized()
     }
 }
 
-/// Parallel hashing - FIXED (no allocation, direct state mixing)
-pub fn blitz_hash_parallel(seed: u64, data: &[u8], num_threads: usize) -> [u8; 32] {
-    use rayon::prelude::*;
+/// A 32-byte digest as a value type - `Copy`, hashable, and parses from its hex form
+///
+/// ```
+/// # use blitzhash::{blitz_digest, BlitzDigest};
+/// let digest = blitz_digest(0, b"key");
+/// let parsed: BlitzDigest = digest.to_string().parse().unwrap();
+/// assert_eq!(parsed, digest);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlitzDigest(pub [u8; 32]);
 
-    if data.len() < 1_000_000 || num_threads <= 1 {
-        return blitz_hash(seed, data);
+impl BlitzDigest {
+    pub fn bytes(&self) -> &[u8; 32] {
+        &self.0
     }
 
-    let chunk_size = (data.len() + num_threads - 1) / num_threads;
-    let chunks: Vec<_> = data.chunks(chunk_size).collect();
+    /// The four little-endian words - see `digest_to_words`
+    pub fn to_words(&self) -> [u64; 4] {
+        digest_to_words(&self.0)
+    }
 
-    // Return partial STATES not bytes - no serialization overhead
-    let partial_states: Vec<[u64; 4]> = chunks
-        .par_iter()
-        .enumerate()
-        .map(|(idx, chunk)| {
-            let hash = blitz_hash(seed.wrapping_add(idx as u64), chunk);
-            // Convert bytes back to u64 states
-            [
-                u64::from_le_bytes(hash[0..8].try_into().unwrap()),
-                u64::from_le_bytes(hash[8..16].try_into().unwrap()),
-                u64::from_le_bytes(hash[16..24].try_into().unwrap()),
-                u64::from_le_bytes(hash[24..32].try_into().unwrap()),
-            ]
-        })
-        .collect();
-
-    // Combine states directly - NO ALLOCATION, NO RE-HASH
-    let mut final_state = [seed ^ K1, seed ^ K2, seed ^ K3, seed ^ K4];
-    for partial in partial_states {
-        final_state[0] = mix_chunk(final_state[0], partial[0], K1);
-        final_state[1] = mix_chunk(final_state[1], partial[1], K2);
-        final_state[2] = mix_chunk(final_state[2], partial[2], K3);
-        final_state[3] = mix_chunk(final_state[3], partial[3], K4);
+    pub fn from_words(words: [u64; 4]) -> Self {
+        BlitzDigest(words_to_digest(&words))
     }
+}
 
-    // Final avalanche
-    for _ in 0..4 {
-        final_state[0] = final_state[0].wrapping_mul(K1) ^ final_state[0].rotate_right(29);
-        final_state[1] = final_state[1].wrapping_mul(K2) ^ final_state[1].rotate_right(31);
-        final_state[2] = final_state[2].wrapping_mul(K3) ^ final_state[2].rotate_right(33);
-        final_state[3] = final_state[3].wrapping_mul(K4) ^ final_state[3].rotate_right(37);
+impl From<[u8; 32]> for BlitzDigest {
+    fn from(bytes: [u8; 32]) -> Self {
+        BlitzDigest(bytes)
     }
+}
 
-    let mut output = [0u8; 32];
-    output[0..8].copy_from_slice(&final_state[0].to_le_bytes());
-    output[8..16].copy_from_slice(&final_state[1].to_le_bytes());
-    output[16..24].copy_from_slice(&final_state[2].to_le_bytes());
-    output[24..32].copy_from_slice(&final_state[3].to_le_bytes());
-    output
+impl fmt::Display for BlitzDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&digest_hex(&self.0))
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl FromStr for BlitzDigest {
+    type Err = BlitzError;
 
-    #[test]
-    fn test_deterministic() {
-        let data = b"Hello, BlitzHash!";
-        let h1 = blitz_hash(0, data);
-        let h2 = blitz_hash(0, data);
-        assert_eq!(h1, h2);
+    /// Parse 64 hex characters, either case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(s, &mut bytes).map_err(|_| BlitzError::InvalidHex)?;
+        Ok(BlitzDigest(bytes))
     }
+}
 
-    #[test]
-    fn test_different_seeds() {
-        let data = b"test data";
-        let h1 = blitz_hash(0, data);
-        let h2 = blitz_hash(1, data);
-        assert_ne!(h1, h2);
+/// `blitz_hash` wrapped as a `BlitzDigest`
+pub fn blitz_digest(seed: u64, data: &[u8]) -> BlitzDigest {
+    BlitzDigest(blitz_hash(seed, data))
+}
+
+/// `Copy` checkpoint of a `BlitzState` - lanes, pending partial block and total length
+///
+/// Resume at `absorbed()`, which equals the state's `total_absorbed()` when the
+/// snapshot was taken: `snapshot.finalize(&data[snapshot.absorbed() as usize..])`.
+#[derive(Debug, Clone, Copy)]
+pub struct BlitzSnapshot {
+    state: [u64; 4],
+    pending: [u8; 32],
+    pending_len: usize,
+    total_len: u64,
+}
+
+impl BlitzSnapshot {
+    /// Bytes absorbed before the snapshot, pending ones included - `tail` starts here
+    pub fn absorbed(&self) -> u64 {
+        self.total_len
     }
 
-    #[test]
-    fn test_streaming_matches_oneshot() {
+    /// Digest of everything absorbed before the snapshot followed by `tail`
+    pub fn finalize(self, mut tail: &[u8]) -> [u8; 32] {
+        let mut state = self.state;
+        let mut folded = self.total_len - self.pending_len as u64;
+        let mut block = self.pending;
+        let mut len = self.pending_len;
+
+        // Complete the pending block first, as `absorb` would
+        if len > 0 && len + tail.len() >= 32 {
+            let take = 32 - len;
+            block[len..].copy_from_slice(&tail[..take]);
+            fold_blocks(&mut state, &block);
+            folded += 32;
+            tail = &tail[take..];
+            len = 0;
+        }
+        if len == 0 {
+            return hash_lanes(state, folded, tail);
+        }
+
+        block[len..len + tail.len()].copy_from_slice(tail);
+        hash_lanes(state, folded, &block[..len + tail.len()])
+    }
+}
+
+/// Byte iterators - `state.extend(bytes)` equals absorbing them as one slice
+impl Extend<u8> for BlitzState {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for byte in iter {
+            self.absorb_byte(byte);
+        }
+    }
+}
+
+/// Slice iterators - one `absorb` per item
+impl<'a> Extend<&'a [u8]> for BlitzState {
+    fn extend<I: IntoIterator<Item = &'a [u8]>>(&mut self, iter: I) {
+        for piece in iter {
+            self.absorb(piece);
+        }
+    }
+}
+
+/// Every single-thread backend `backend_name` can report
+pub const BACKENDS: &[&str] = &["scalar", "avx2", "neon", "portable-simd"];
+
+/// Name of the single-thread backend behind `blitz_hash` in this build
+///
+/// "portable-simd" with that feature, "scalar" otherwise; the intrinsic names are
+/// reserved so callers can label results without caring which one is compiled in.
+pub fn backend_name() -> &'static str {
+    single_thread_path().as_str()
+}
+
+/// Code path that produced a digest - see `blitz_hash_explain` and `blitz_hash_auto_explain`
+///
+/// Non-exhaustive: new backends get new variants, so match with a `_` arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BlitzPath {
+    Scalar,
+    /// Reserved, like its `BACKENDS` name - no AVX2 loop is compiled in yet
+    Avx2,
+    /// Reserved, like its `BACKENDS` name - no NEON loop is compiled in yet
+    Neon,
+    /// `std::simd` block loop (`portable-simd` feature)
+    PortableSimd,
+    /// Fixed-block parallel path, whatever the thread count
+    Parallel,
+}
+
+impl BlitzPath {
+    /// Lowercase name, as used in `BACKENDS` and bench labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlitzPath::Scalar => "scalar",
+            BlitzPath::Avx2 => "avx2",
+            BlitzPath::Neon => "neon",
+            BlitzPath::PortableSimd => "portable-simd",
+            BlitzPath::Parallel => "parallel",
+        }
+    }
+}
+
+/// Backend `blitz_hash` runs on in this build
+#[inline]
+fn single_thread_path() -> BlitzPath {
+    if cfg!(feature = "portable-simd") {
+        BlitzPath::PortableSimd
+    } else {
+        BlitzPath::Scalar
+    }
+}
+
+/// `blitz_hash` plus the code path that computed it, for performance logging
+pub fn blitz_hash_explain(seed: u64, data: &[u8]) -> ([u8; 32], BlitzPath) {
+    (blitz_hash(seed, data), single_thread_path())
+}
+
+/// Streaming API (kept for compatibility)
+///
+/// Buffers up to one 32-byte block so the lane mixing is identical to `blitz_hash`.
+///
+/// ```
+/// use blitzhash::{blitz_hash, BlitzState};
+///
+/// let mut hasher = BlitzState::new(42);
+/// hasher.absorb(b"part 1");
+/// hasher.absorb(b"part 2");
+/// let digest = hasher.finalize();
+///
+/// # #[cfg(not(feature = "legacy-3-rounds"))]
+/// assert_eq!(hex::encode(&digest[..8]), "db031e857a28d058");
+/// assert_eq!(digest, blitz_hash(42, b"part 1part 2"));
+/// ```
+///
+/// Cloning forks the computation: a clone taken at any point, partial block included,
+/// continues exactly like the original, so a shared prefix is hashed only once.
+///
+/// ```
+/// # use blitzhash::{blitz_hash, BlitzState};
+/// let mut prefix = BlitzState::new(0);
+/// prefix.absorb(b"GET /api/");
+///
+/// let mut users = prefix.clone();
+/// users.absorb(b"users");
+/// let mut items = prefix;
+/// items.absorb(b"items");
+///
+/// assert_eq!(users.finalize(), blitz_hash(0, b"GET /api/users"));
+/// assert_eq!(items.finalize(), blitz_hash(0, b"GET /api/items"));
+/// ```
+#[derive(Clone)]
+pub struct BlitzState {
+    state: [u64; 4],
+    buffer: [u8; 32],
+    buffer_len: usize,
+    total_len: u64,
+    #[cfg(all(feature = "debug-integrity", debug_assertions))]
+    integrity: Integrity,
+}
+
+/// Shadow bookkeeping for the `debug-integrity` self-check, kept apart from the lanes
+///
+/// Debug builds only: `record` runs a full `blitz_hash64` per `absorb`, which a release
+/// build shouldn't pay for when nothing would assert on it. `running` is not checked
+/// here - it is what `integrity_hash64` returns, for callers comparing two replays.
+#[cfg(all(feature = "debug-integrity", debug_assertions))]
+#[derive(Clone)]
+struct Integrity {
+    absorbed: u64,
+    running: u64,
+    /// Last 32 bytes absorbed - the pending buffer must be a suffix of these
+    recent: [u8; 32],
+}
+
+#[cfg(all(feature = "debug-integrity", debug_assertions))]
+impl Integrity {
+    fn new(seed: u64) -> Self {
+        Self { absorbed: 0, running: seed, recent: [0u8; 32] }
+    }
+
+    fn record(&mut self, data: &[u8]) {
+        self.absorbed += data.len() as u64;
+        self.running = blitz_hash64(self.running, data);
+        if data.len() >= 32 {
+            self.recent.copy_from_slice(&data[data.len() - 32..]);
+        } else {
+            self.recent.rotate_left(data.len());
+            self.recent[32 - data.len()..].copy_from_slice(data);
+        }
+    }
+
+    /// Length accounting and pending buffer must agree with what was recorded
+    fn check(&self, total_len: u64, pending: &[u8]) {
+        debug_assert_eq!(self.absorbed, total_len, "BlitzState lost track of absorbed length");
+        debug_assert_eq!(
+            (total_len - pending.len() as u64) % 32,
+            0,
+            "BlitzState folded a partial block"
+        );
+        debug_assert_eq!(
+            &self.recent[32 - pending.len()..],
+            pending,
+            "BlitzState buffer doesn't hold the last bytes absorbed"
+        );
+    }
+}
+
+impl BlitzState {
+    /// Fresh state for `seed` - finalizing it right away gives `blitz_hash(seed, b"")`
+    ///
+    /// ```
+    /// # use blitzhash::{blitz_hash, BlitzState};
+    /// assert_eq!(BlitzState::new(7).finalize(), blitz_hash(7, b""));
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        Self::from_state_key([seed; 4])
+    }
+
+    /// Start from a 128-bit seed - see `blitz_hash_seed128`
+    pub fn with_seed128(seed: [u8; 16]) -> Self {
+        Self::from_state_key(seed128_key(seed))
+    }
+
+    /// Start from a full 256-bit key - `new(seed)` is `from_state_key([seed; 4])`
+    pub fn from_state_key(key: [u64; 4]) -> Self {
+        Self {
+            state: key_state(key),
+            buffer: [0u8; 32],
+            buffer_len: 0,
+            total_len: 0,
+            #[cfg(all(feature = "debug-integrity", debug_assertions))]
+            integrity: Integrity::new(key[0]),
+        }
+    }
+
+    /// Start substream `index` of a merged digest - see `merge`
+    pub fn substream(seed: u64, index: usize) -> Self {
+        Self::new(block_seed(seed, index))
+    }
+
+    /// Combine independently absorbed substreams, in slice order, into one digest
+    ///
+    /// Each `states[i]` must come from `substream(seed, i)`. This is the
+    /// `blitz_hash_parallel` combine: when every substream but the last holds exactly
+    /// `PARALLEL_BLOCK_SIZE` bytes, the result equals
+    /// `blitz_hash_parallel_with(seed, data, _, 0)` over the concatenation. It does NOT
+    /// equal `blitz_hash` of the concatenation, and any other split gives a digest of
+    /// its own - the split points are part of the result.
+    ///
+    /// ```
+    /// # use blitzhash::{blitz_hash_parallel_with, BlitzState, PARALLEL_BLOCK_SIZE};
+    /// let data = vec![7u8; 3 * PARALLEL_BLOCK_SIZE + 100];
+    /// let states: Vec<BlitzState> = data
+    ///     .chunks(PARALLEL_BLOCK_SIZE)
+    ///     .enumerate()
+    ///     .map(|(i, part)| {
+    ///         let mut state = BlitzState::substream(42, i);
+    ///         state.absorb(part);
+    ///         state
+    ///     })
+    ///     .collect();
+    /// assert_eq!(BlitzState::merge(42, &states), blitz_hash_parallel_with(42, &data, 4, 0));
+    /// ```
+    pub fn merge(seed: u64, states: &[BlitzState]) -> [u8; 32] {
+        let mut combined = combine_start(seed);
+        for state in states {
+            combine_absorb(&mut combined, &digest_to_words(&state.clone().finalize()));
+        }
+        combine_finish(combined)
+    }
+
+    /// Mix one full 32-byte block - same lane layout as the one-shot loop
+    #[inline(always)]
+    fn mix_block(&mut self, block: &[u8; 32]) {
+        fold_blocks(&mut self.state, block);
+    }
+
+    /// Absorb one field of a composite key, prefixed by its length as a little-endian u64
+    pub fn absorb_framed(&mut self, field: &[u8]) {
+        self.absorb(&(field.len() as u64).to_le_bytes());
+        self.absorb(field);
+    }
+
+    /// Current lane values - bytes still in the buffer aren't folded in yet
+    ///
+    /// With the `trace` feature every block, word, tail and finalize step also logs
+    /// its lanes to stderr, for checking a port step by step.
+    pub fn state_words(&self) -> [u64; 4] {
+        self.state
+    }
+
+    /// Bytes passed to `absorb` so far, buffered ones included
+    pub fn total_absorbed(&self) -> u64 {
+        self.total_len
+    }
+
+    /// Alias of `total_absorbed`
+    pub fn bytes_absorbed(&self) -> u64 {
+        self.total_len
+    }
+
+    /// Bytes buffered but not yet folded into the lanes - always below 32 (one block)
+    pub fn pending_bytes(&self) -> usize {
+        self.buffer_len
+    }
+
+    /// `Copy` checkpoint including the pending bytes - see `BlitzSnapshot`
+    ///
+    /// Resume with `snapshot.finalize(&data[snapshot.absorbed() as usize..])`.
+    pub fn snapshot(&self) -> BlitzSnapshot {
+        BlitzSnapshot {
+            state: self.state,
+            pending: self.buffer,
+            pending_len: self.buffer_len,
+            total_len: self.total_len,
+        }
+    }
+
+    /// Feed more input - any split of the message gives the same digest
+    ///
+    /// ```
+    /// # use blitzhash::{blitz_hash, BlitzState};
+    /// let mut hasher = BlitzState::new(0);
+    /// for piece in [&b"Hello"[..], b", ", b"world!"] {
+    ///     hasher.absorb(piece);
+    /// }
+    /// assert_eq!(hasher.finalize(), blitz_hash(0, b"Hello, world!"));
+    /// ```
+    pub fn absorb(&mut self, data: &[u8]) {
+        // Zero-length reads are a no-op - never touch the buffer state
+        if data.is_empty() {
+            return;
+        }
+
+        #[cfg(all(feature = "debug-integrity", debug_assertions))]
+        self.integrity.record(data);
+
+        let mut pos = 0;
+        self.total_len += data.len() as u64;
+
+        // Handle buffered bytes first
+        if self.buffer_len > 0 {
+            let needed = 32 - self.buffer_len;
+            let available = data.len().min(needed);
+            self.buffer[self.buffer_len..self.buffer_len + available]
+                .copy_from_slice(&data[..available]);
+            self.buffer_len += available;
+            pos += available;
+
+            if self.buffer_len == 32 {
+                let block = self.buffer;
+                self.mix_block(&block);
+                self.buffer_len = 0;
+            }
+        }
+
+        // Process full 32-byte blocks straight from the input
+        pos += fold_blocks(&mut self.state, &data[pos..]);
+
+        // Buffer remaining bytes
+        if pos < data.len() {
+            let remaining = data.len() - pos;
+            self.buffer[..remaining].copy_from_slice(&data[pos..]);
+            self.buffer_len = remaining;
+        }
+    }
+
+    /// Feed a single byte - same result as `absorb(&[byte])`, without the slice plumbing
+    #[inline]
+    pub fn absorb_byte(&mut self, byte: u8) {
+        #[cfg(all(feature = "debug-integrity", debug_assertions))]
+        self.integrity.record(&[byte]);
+
+        self.buffer[self.buffer_len] = byte;
+        self.buffer_len += 1;
+        self.total_len += 1;
+        if self.buffer_len == 32 {
+            let block = self.buffer;
+            self.mix_block(&block);
+            self.buffer_len = 0;
+        }
+    }
+
+    /// Consume the state and produce the 32-byte digest
+    ///
+    /// ```
+    /// # use blitzhash::BlitzState;
+    /// let mut hasher = BlitzState::new(0);
+    /// hasher.absorb(b"Hello, world!");
+    /// let digest = hasher.finalize();
+    /// # #[cfg(not(feature = "legacy-3-rounds"))]
+    /// assert_eq!(hex::encode(&digest[..8]), "6ad182d13ceab0b7");
+    /// ```
+    pub fn finalize(self) -> [u8; 32] {
+        #[cfg(all(feature = "debug-integrity", debug_assertions))]
+        self.integrity.check(self.total_len, &self.buffer[..self.buffer_len]);
+
+        // The buffer is the unfinished tail of the one-shot loop - run the same code on it
+        let folded = self.total_len - self.buffer_len as u64;
+        hash_lanes(self.state, folded, &self.buffer[..self.buffer_len])
+    }
+
+    /// Chained `blitz_hash64` of every `absorb` call so far (`debug-integrity`, debug builds only)
+    ///
+    /// Depends on how the input was split, unlike the digest: replaying the same calls
+    /// over the same bytes reproduces it, so a source buffer changed in between shows up.
+    #[cfg(all(feature = "debug-integrity", debug_assertions))]
+    pub fn integrity_hash64(&self) -> u64 {
+        self.integrity.running
+    }
+
+    /// 64-bit digest - equals `blitz_hash64` over everything absorbed
+    pub fn finalize64(self) -> u64 {
+        fold64(&self.finalize())
+    }
+
+    /// `finalize64` without consuming the state, so absorbing can continue
+    pub fn finish64(&self) -> u64 {
+        self.clone().finalize64()
+    }
+
+    /// Finalize with a per-message nonce - equals `blitz_hash_nonce` over everything absorbed
+    pub fn finalize_with_nonce(self, nonce: u64) -> [u8; 32] {
+        #[cfg(all(feature = "debug-integrity", debug_assertions))]
+        self.integrity.check(self.total_len, &self.buffer[..self.buffer_len]);
+
+        let state = fold_input(self.state, &self.buffer[..self.buffer_len]);
+        finish_nonce(state, self.total_len, nonce)
+    }
+
+    /// Finalize into a `FinalizedBlitz` - the post-finalize surface has no `absorb`
+    pub fn into_finalized(self) -> FinalizedBlitz {
+        FinalizedBlitz(self.finalize())
+    }
+
+    /// Finalize with a chosen word byte order - `Endian::Little` is `finalize`
+    pub fn finalize_with_endian(self, endian: Endian) -> [u8; 32] {
+        endian.apply(self.finalize())
+    }
+
+    /// Finalize straight into `out[..32]` (e.g. at an offset in a packet buffer)
+    ///
+    /// Bytes past the first 32 are left untouched.
+    pub fn finalize_to_slice(self, out: &mut [u8]) -> Result<(), BlitzError> {
+        if out.len() < 32 {
+            return Err(BlitzError::OutputTooSmall { len: out.len() });
+        }
+        out[..32].copy_from_slice(&self.finalize());
+        Ok(())
+    }
+}
+
+/// Merkle-style parent of two child digests (order-sensitive)
+///
+/// Works directly on the eight u64 words with the same fold as the parallel
+/// combine - no byte buffer, no length mixing, so it is NOT `blitz_hash` of
+/// `left ++ right`.
+pub fn blitz_combine(seed: u64, left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut state = combine_start(seed);
+    combine_absorb(&mut state, &digest_to_words(left));
+    combine_absorb(&mut state, &digest_to_words(right));
+    combine_finish(state)
+}
+
+/// Inputs shorter than this are hashed serially by `blitz_hash_parallel`
+pub const DEFAULT_MIN_PARALLEL_LEN: usize = 1_000_000;
+
+/// Fixed block size for the parallel path - the digest never depends on thread count
+pub const PARALLEL_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Parallel hashing with the default serial cutoff
+///
+/// ```
+/// use blitzhash::{blitz_hash, blitz_hash_parallel};
+///
+/// let data = vec![7u8; 2_000_000];
+/// let digest = blitz_hash_parallel(0, &data, 4);
+/// # #[cfg(not(feature = "legacy-3-rounds"))]
+/// assert_eq!(hex::encode(&digest[..8]), "3b33869d9e4f9d9a");
+/// assert_eq!(digest, blitz_hash_parallel(0, &data, 8));
+///
+/// // Short inputs are hashed serially
+/// assert_eq!(blitz_hash_parallel(0, b"tiny", 4), blitz_hash(0, b"tiny"));
+/// ```
+pub fn blitz_hash_parallel(seed: u64, data: &[u8], num_threads: usize) -> [u8; 32] {
+    blitz_hash_parallel_with(seed, data, num_threads, DEFAULT_MIN_PARALLEL_LEN)
+}
+
+/// Parallel hashing with a caller-chosen serial cutoff
+///
+/// Inputs shorter than `min_parallel_len`, and empty input, return exactly
+/// `blitz_hash(seed, data)`.
+/// Longer inputs are split into fixed `PARALLEL_BLOCK_SIZE` blocks whose states are
+/// combined in order, so the digest is the same for every `num_threads`.
+pub fn blitz_hash_parallel_with(
+    seed: u64,
+    data: &[u8],
+    num_threads: usize,
+    min_parallel_len: usize,
+) -> [u8; 32] {
+    if !takes_block_path(data.len(), min_parallel_len) {
+        return blitz_hash(seed, data);
+    }
+
+    let partial_states = block_states(seed, 0, data, num_threads);
+    combine_block_states(seed, &partial_states)
+}
+
+/// Whether `blitz_hash_parallel_with` hashes `len` bytes in blocks rather than serially
+///
+/// Empty input has no blocks to combine - any cutoff, even 0, hashes it serially.
+#[inline]
+fn takes_block_path(len: usize, min_parallel_len: usize) -> bool {
+    len >= min_parallel_len && len > 0
+}
+
+/// Parallel hashing sized to this machine - one thread per available core
+///
+/// The digest is `blitz_hash_parallel`'s, which never depends on thread count, so the
+/// result is the same on every machine. Falls back to one thread when
+/// `available_parallelism` can't tell.
+pub fn blitz_hash_auto(seed: u64, data: &[u8]) -> [u8; 32] {
+    blitz_hash_auto_with(seed, data, DEFAULT_MIN_PARALLEL_LEN)
+}
+
+/// `blitz_hash_auto` with a caller-chosen serial cutoff - see `blitz_hash_parallel_with`
+pub fn blitz_hash_auto_with(seed: u64, data: &[u8], min_parallel_len: usize) -> [u8; 32] {
+    blitz_hash_parallel_with(seed, data, auto_threads(), min_parallel_len)
+}
+
+/// `blitz_hash_auto` plus the code path - `Parallel` once the input reaches the cutoff
+pub fn blitz_hash_auto_explain(seed: u64, data: &[u8]) -> ([u8; 32], BlitzPath) {
+    let path = if takes_block_path(data.len(), DEFAULT_MIN_PARALLEL_LEN) {
+        BlitzPath::Parallel
+    } else {
+        single_thread_path()
+    };
+    (blitz_hash_auto(seed, data), path)
+}
+
+/// Threads `blitz_hash_auto` uses - 1 if the platform can't report its parallelism
+fn auto_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Parallel hashing on a dedicated Rayon pool instead of the global one
+///
+/// Same digests as `blitz_hash_parallel` - the pool only decides where the blocks run.
+///
+/// ```
+/// # use blitzhash::{blitz_hash_parallel, BlitzParallel};
+/// let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+/// let hasher = BlitzParallel::with_pool(pool);
+/// let data = vec![1u8; 2_000_000];
+/// assert_eq!(hasher.hash(0, &data), blitz_hash_parallel(0, &data, 8));
+/// ```
+#[cfg(all(feature = "rayon", not(feature = "std-threads"), not(target_arch = "wasm32")))]
+pub struct BlitzParallel {
+    pool: rayon::ThreadPool,
+}
+
+#[cfg(all(feature = "rayon", not(feature = "std-threads"), not(target_arch = "wasm32")))]
+impl BlitzParallel {
+    pub fn with_pool(pool: rayon::ThreadPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn pool(&self) -> &rayon::ThreadPool {
+        &self.pool
+    }
+
+    /// `blitz_hash_parallel` across every thread of the pool
+    pub fn hash(&self, seed: u64, data: &[u8]) -> [u8; 32] {
+        let threads = self.pool.current_num_threads();
+        self.pool.install(|| blitz_hash_parallel(seed, data, threads))
+    }
+}
+
+/// Read size used by `blitz_hash_parallel_reader`
+pub const DEFAULT_READ_SIZE: usize = 16 * PARALLEL_BLOCK_SIZE;
+
+/// Parallel hashing of a `Read` source that doesn't fit in memory
+///
+/// Produces the same digest as `blitz_hash_parallel` over the full contents. With
+/// `num_threads > 1` each batch is hashed on the pool while the next one is read, so
+/// I/O overlaps with hashing; reads stay on the calling thread, so `R` needn't be `Send`.
+pub fn blitz_hash_parallel_reader<R: Read>(
+    seed: u64,
+    reader: R,
+    num_threads: usize,
+) -> io::Result<[u8; 32]> {
+    blitz_hash_parallel_reader_with(seed, reader, num_threads, DEFAULT_READ_SIZE)
+}
+
+/// Parallel hashing of a `Read` source, `read_size` bytes per batch
+///
+/// `read_size` is rounded up to a whole number of `PARALLEL_BLOCK_SIZE` blocks, so it
+/// only trades memory for thread utilisation - the digest never depends on it. Two
+/// batches are held at once: the one being hashed and the one being read.
+pub fn blitz_hash_parallel_reader_with<R: Read>(
+    seed: u64,
+    mut reader: R,
+    num_threads: usize,
+    read_size: usize,
+) -> io::Result<[u8; 32]> {
+    let read_size = read_size.max(1).next_multiple_of(PARALLEL_BLOCK_SIZE);
+
+    // First batch must cover the serial cutoff to know which path applies
+    let first_size = read_size.max(DEFAULT_MIN_PARALLEL_LEN.next_multiple_of(PARALLEL_BLOCK_SIZE));
+    let mut buffer = vec![0u8; first_size];
+    let mut filled = read_full(&mut reader, &mut buffer)?;
+    if filled < DEFAULT_MIN_PARALLEL_LEN {
+        return Ok(blitz_hash(seed, &buffer[..filled]));
+    }
+
+    let mut combined = combine_start(seed);
+    let mut next_block = 0;
+    let mut spare = vec![0u8; read_size];
+    loop {
+        // A short batch means EOF was reached - there is nothing more to read
+        let more = filled == buffer.len();
+        let batch = &buffer[..filled];
+        let (states, next_filled) = hash_while_reading(
+            || block_states(seed, next_block, batch, num_threads),
+            || if more { read_full(&mut reader, &mut spare) } else { Ok(0) },
+            num_threads,
+        );
+        for partial in states {
+            combine_absorb(&mut combined, &partial);
+        }
+        next_block += filled.div_ceil(PARALLEL_BLOCK_SIZE);
+
+        filled = next_filled?;
+        if filled == 0 {
+            break;
+        }
+        std::mem::swap(&mut buffer, &mut spare);
+        spare.truncate(read_size);
+    }
+
+    Ok(combine_finish(combined))
+}
+
+/// Hash one batch on a helper thread while the caller reads the next
+#[cfg(all(any(feature = "rayon", feature = "std-threads"), not(target_arch = "wasm32")))]
+fn hash_while_reading<T>(
+    hash: impl FnOnce() -> Vec<[u64; 4]> + Send,
+    read: impl FnOnce() -> T,
+    num_threads: usize,
+) -> (Vec<[u64; 4]>, T) {
+    if num_threads <= 1 {
+        return (hash(), read());
+    }
+    std::thread::scope(|scope| {
+        let hashing = scope.spawn(hash);
+        let read = read();
+        let states = hashing.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+        (states, read)
+    })
+}
+
+/// No threads to overlap with - hash, then read
+#[cfg(not(all(any(feature = "rayon", feature = "std-threads"), not(target_arch = "wasm32"))))]
+fn hash_while_reading<T>(
+    hash: impl FnOnce() -> Vec<[u64; 4]>,
+    read: impl FnOnce() -> T,
+    _num_threads: usize,
+) -> (Vec<[u64; 4]>, T) {
+    (hash(), read())
+}
+
+/// Fill `buf` unless EOF comes first - returns bytes read
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// States of the blocks in `data`, numbered from `first_block`
+fn block_states(seed: u64, first_block: usize, data: &[u8], num_threads: usize) -> Vec<[u64; 4]> {
+    if num_threads <= 1 {
+        serial_block_states(seed, first_block, data)
+    } else {
+        threaded_block_states(seed, first_block, data, num_threads)
+    }
+}
+
+fn serial_block_states(seed: u64, first_block: usize, data: &[u8]) -> Vec<[u64; 4]> {
+    data.chunks(PARALLEL_BLOCK_SIZE)
+        .enumerate()
+        .map(|(idx, block)| block_state(seed, first_block + idx, block))
+        .collect()
+}
+
+#[cfg(all(feature = "std-threads", not(target_arch = "wasm32")))]
+use scoped_block_states as threaded_block_states;
+
+#[cfg(all(feature = "rayon", not(feature = "std-threads"), not(target_arch = "wasm32")))]
+use rayon_block_states as threaded_block_states;
+
+/// No threading backend (or wasm32, which can't spawn threads) - same blocks, one thread
+#[cfg(any(target_arch = "wasm32", not(any(feature = "rayon", feature = "std-threads"))))]
+fn threaded_block_states(
+    seed: u64,
+    first_block: usize,
+    data: &[u8],
+    _num_threads: usize,
+) -> Vec<[u64; 4]> {
+    serial_block_states(seed, first_block, data)
+}
+
+/// Rayon backend - at most `num_threads` contiguous runs of blocks
+#[cfg(feature = "rayon")]
+#[cfg_attr(any(feature = "std-threads", target_arch = "wasm32"), allow(dead_code))]
+fn rayon_block_states(
+    seed: u64,
+    first_block: usize,
+    data: &[u8],
+    num_threads: usize,
+) -> Vec<[u64; 4]> {
+    use rayon::prelude::*;
+
+    let blocks = data.len().div_ceil(PARALLEL_BLOCK_SIZE);
+    data.par_chunks(PARALLEL_BLOCK_SIZE)
+        .with_min_len(blocks.div_ceil(num_threads).max(1))
+        .enumerate()
+        .map(|(idx, block)| block_state(seed, first_block + idx, block))
+        .collect()
+}
+
+/// Scoped-thread backend - each thread fills its own slice of a preallocated state array
+///
+/// Needs only std, so it is also built for tests and checked against the other backends.
+#[cfg(any(feature = "std-threads", test))]
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn scoped_block_states(
+    seed: u64,
+    first_block: usize,
+    data: &[u8],
+    num_threads: usize,
+) -> Vec<[u64; 4]> {
+    let blocks = data.len().div_ceil(PARALLEL_BLOCK_SIZE);
+    let mut states = vec![[0u64; 4]; blocks];
+    let per_thread = blocks.div_ceil(num_threads).max(1);
+
+    std::thread::scope(|scope| {
+        for (run, out) in states.chunks_mut(per_thread).enumerate() {
+            scope.spawn(move || {
+                for (i, slot) in out.iter_mut().enumerate() {
+                    let idx = run * per_thread + i;
+                    let start = idx * PARALLEL_BLOCK_SIZE;
+                    let end = (start + PARALLEL_BLOCK_SIZE).min(data.len());
+                    *slot = block_state(seed, first_block + idx, &data[start..end]);
+                }
+            });
+        }
+    });
+
+    states
+}
+
+/// Hash one parallel block and return its partial STATE not bytes
+#[inline]
+fn block_state(seed: u64, idx: usize, block: &[u8]) -> [u64; 4] {
+    digest_to_words(&blitz_hash(block_seed(seed, idx), block))
+}
+
+/// Per-block seed - mixed, not `seed + idx`, so block `i` of seed `S` never
+/// reuses the seed of block `i - 1` under `S + 1`
+#[inline]
+fn block_seed(seed: u64, idx: usize) -> u64 {
+    mix_chunk(mix_chunk(seed, 0, K1), idx as u64, K2)
+}
+
+/// Combine block states in order - NO ALLOCATION, NO RE-HASH
+fn combine_block_states(seed: u64, partial_states: &[[u64; 4]]) -> [u8; 32] {
+    let mut final_state = combine_start(seed);
+    for partial in partial_states {
+        combine_absorb(&mut final_state, partial);
+    }
+    combine_finish(final_state)
+}
+
+#[inline]
+fn combine_start(seed: u64) -> [u64; 4] {
+    [seed ^ K1, seed ^ K2, seed ^ K3, seed ^ K4]
+}
+
+#[inline]
+fn combine_absorb(final_state: &mut [u64; 4], partial: &[u64; 4]) {
+    final_state[0] = mix_chunk(final_state[0], partial[0], K1);
+    final_state[1] = mix_chunk(final_state[1], partial[1], K2);
+    final_state[2] = mix_chunk(final_state[2], partial[2], K3);
+    final_state[3] = mix_chunk(final_state[3], partial[3], K4);
+}
+
+fn combine_finish(mut final_state: [u64; 4]) -> [u8; 32] {
+    avalanche(&mut final_state);
+    words_to_digest(&final_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hamming(a: &[u8; 32], b: &[u8; 32]) -> u32 {
+        a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum()
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let data = b"Hello, BlitzHash!";
+        let h1 = blitz_hash(0, data);
+        let h2 = blitz_hash(0, data);
+        assert_eq!(h1, h2);
+    }
+
+    #[test]
+    fn test_different_seeds() {
+        let data = b"test data";
+        let h1 = blitz_hash(0, data);
+        let h2 = blitz_hash(1, data);
+        assert_ne!(h1, h2);
+    }
+
+    #[test]
+    fn test_streaming_matches_oneshot() {
         let data = b"The quick brown fox jumps over the lazy dog";
         let oneshot = blitz_hash(42, data);
         
@@ -308,4 +1608,983 @@ mod tests {
         assert_ne!(h1, h3);
         assert_ne!(h2, h3);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parallel_below_threshold_is_serial() {
+        let data = vec![0xA5u8; 300_000];
+        let serial = blitz_hash(7, &data);
+        for threads in [1, 2, 8] {
+            assert_eq!(blitz_hash_parallel_with(7, &data, threads, 300_001), serial);
+            assert_eq!(blitz_hash_parallel(7, &data, threads), serial);
+        }
+    }
+
+    #[test]
+    fn test_parallel_above_threshold_is_deterministic() {
+        let data: Vec<u8> = (0..1_500_000u32).map(|i| (i * 31 + 7) as u8).collect();
+        let reference = blitz_hash_parallel_with(7, &data, 1, 256 * 1024);
+        for threads in [2, 3, 8, 64] {
+            assert_eq!(blitz_hash_parallel_with(7, &data, threads, 256 * 1024), reference);
+        }
+        assert_eq!(blitz_hash_parallel(7, &data, 4), reference);
+    }
+
+    #[test]
+    fn test_rayon_and_scoped_backends_agree() {
+        for len in [0, 1, PARALLEL_BLOCK_SIZE - 1, PARALLEL_BLOCK_SIZE, 5 * PARALLEL_BLOCK_SIZE + 17, 2_000_000] {
+            let data: Vec<u8> = (0..len as u32).map(|i| (i * 131 + 3) as u8).collect();
+            let serial = serial_block_states(9, 0, &data);
+            for threads in [2, 3, 8] {
+                let scoped = scoped_block_states(9, 0, &data, threads);
+                assert_eq!(scoped, serial, "len {} threads {}", len, threads);
+                #[cfg(feature = "rayon")]
+                assert_eq!(rayon_block_states(9, 0, &data, threads), scoped);
+            }
+        }
+    }
+
+    #[test]
+    fn test_keyed256_key_bit_moves_whole_digest() {
+        let data = b"keyed dedup record";
+        let base = [1u64, 2, 3, 4];
+        let h0 = blitz_hash_keyed256(base, data);
+        if !cfg!(feature = "legacy-3-rounds") {
+            assert_eq!(
+                hex::encode(h0),
+                "8453be47e22d20ef7af7165843670f8a751f17c2cb9bad5f0b082d6e7d0ac5d9"
+            );
+        }
+        for lane in 0..4 {
+            for bit in [0, 31, 63] {
+                let mut key = base;
+                key[lane] ^= 1 << bit;
+                let h1 = blitz_hash_keyed256(key, data);
+                // A fresh 256-bit digest is ~128 bits away
+                let distance = hamming(&h0, &h1);
+                assert!((96..=160).contains(&distance), "key[{}] bit {} moved {} bits", lane, bit, distance);
+                for (w0, w1) in digest_to_words(&h0).into_iter().zip(digest_to_words(&h1)) {
+                    assert_ne!(w0, w1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_state_key_generalizes_seed() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        for seed in [0, 1, 42, u64::MAX] {
+            let mut keyed = BlitzState::from_state_key([seed; 4]);
+            keyed.absorb(data);
+            let mut seeded = BlitzState::new(seed);
+            seeded.absorb(data);
+            assert_eq!(keyed.finalize(), seeded.finalize());
+            assert_eq!(blitz_hash_keyed256([seed; 4], data), blitz_hash(seed, data));
+        }
+        assert_ne!(blitz_hash_keyed256([0, 0, 0, 1], data), blitz_hash(0, data));
+    }
+
+    #[test]
+    fn test_domain_separation() {
+        for data in [&b""[..], b"report.pdf", b"The quick brown fox jumps over the lazy dog"] {
+            let name = blitz_hash_domain(b"name", 3, data);
+            let content = blitz_hash_domain(b"content", 3, data);
+            assert!(hamming(&name, &content) > 64);
+            assert_ne!(name, blitz_hash(3, data));
+        }
+
+        // Length prefix removes domain ++ data ambiguity
+        assert_ne!(blitz_hash_domain(b"ab", 3, b"c"), blitz_hash_domain(b"a", 3, b"bc"));
+    }
+
+    #[test]
+    fn test_domain_depends_on_seed_and_data() {
+        let h = blitz_hash_domain(b"content", 3, b"payload");
+        assert_eq!(h, blitz_hash_domain(b"content", 3, b"payload"));
+        assert_ne!(h, blitz_hash_domain(b"content", 4, b"payload"));
+        assert_ne!(h, blitz_hash_domain(b"content", 3, b"payloae"));
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    #[test]
+    fn test_parallel_reader_matches_in_memory() {
+        let data: Vec<u8> = (0..3_250_017u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+        let in_memory = blitz_hash_parallel(11, &data, 4);
+        assert_eq!(blitz_hash_parallel_reader(11, io::Cursor::new(&data), 4).unwrap(), in_memory);
+        for read_size in [1, 100_000, PARALLEL_BLOCK_SIZE, 1 << 20] {
+            for threads in [1, 3] {
+                let streamed = blitz_hash_parallel_reader_with(11, io::Cursor::new(&data), threads, read_size);
+                assert_eq!(streamed.unwrap(), in_memory);
+            }
+        }
+
+        // A read error while the previous batch is being hashed still surfaces
+        let failing = io::Cursor::new(&data[..2_000_000]).chain(FailingReader);
+        let err = blitz_hash_parallel_reader_with(11, failing, 3, PARALLEL_BLOCK_SIZE).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+        // Below the serial cutoff it is plain blitz_hash
+        let small = &data[..999_999];
+        assert_eq!(blitz_hash_parallel_reader(11, small, 4).unwrap(), blitz_hash(11, small));
+    }
+
+    #[test]
+    fn test_combine_order_sensitive() {
+        let a = blitz_hash(0, b"left child");
+        let b = blitz_hash(0, b"right child");
+        assert_ne!(blitz_combine(0, &a, &b), blitz_combine(0, &b, &a));
+        assert_eq!(blitz_combine(0, &a, &b), blitz_combine(0, &a, &b));
+        assert_ne!(blitz_combine(0, &a, &b), blitz_combine(1, &a, &b));
+
+        let mut concat = [0u8; 64];
+        concat[..32].copy_from_slice(&a);
+        concat[32..].copy_from_slice(&b);
+        assert_ne!(blitz_combine(0, &a, &b), blitz_hash(0, &concat));
+    }
+
+    #[test]
+    fn test_combine_bit_flips() {
+        let a = blitz_hash(5, b"left child");
+        let b = blitz_hash(5, b"right child");
+        let parent = blitz_combine(5, &a, &b);
+        for bit in [0, 7, 63, 64, 200, 255] {
+            let mut flipped = a;
+            flipped[bit / 8] ^= 1 << (bit % 8);
+            assert_ne!(blitz_combine(5, &flipped, &b), parent);
+
+            let mut flipped = b;
+            flipped[bit / 8] ^= 1 << (bit % 8);
+            assert_ne!(blitz_combine(5, &a, &flipped), parent);
+        }
+    }
+
+    #[test]
+    fn test_backend_name_is_known() {
+        let name = backend_name();
+        assert!(!name.is_empty());
+        assert!(BACKENDS.contains(&name));
+    }
+
+    #[test]
+    fn test_snapshot_resumes_to_oneshot() {
+        let data: Vec<u8> = (0..300u32).map(|i| (i * 7 + 1) as u8).collect();
+        let oneshot = blitz_hash(13, &data);
+
+        let mut state = BlitzState::new(13);
+        let mut pos = 0;
+        for split in [0, 5, 32, 33, 64, 100, 191, 256, 300] {
+            state.absorb(&data[pos..split]);
+            pos = split;
+            let snapshot = state.snapshot();
+            assert_eq!(snapshot.absorbed(), state.total_absorbed());
+            assert_eq!(snapshot.finalize(&data[split..]), oneshot);
+            // Tails that don't complete the pending block, or fill exactly one
+            for end in [split, split + 1, split + 31, split + 32].map(|end| end.min(data.len())) {
+                assert_eq!(snapshot.finalize(&data[split..end]), blitz_hash(13, &data[..end]));
+            }
+        }
+        assert_eq!(state.finalize(), oneshot);
+    }
+
+    #[test]
+    fn test_vectored_matches_concatenation() {
+        let data: Vec<u8> = (0..150u32).map(|i| (i * 13 + 5) as u8).collect();
+        let oneshot = blitz_hash(21, &data);
+        let fragmentations: [&[usize]; 5] = [
+            &[150],
+            &[0, 150, 0],
+            &[1, 0, 31, 0, 0, 40, 78],
+            &[32, 32, 32, 32, 22],
+            &[7, 9, 0, 16, 1, 117],
+        ];
+        for sizes in fragmentations {
+            let mut bufs = Vec::new();
+            let mut pos = 0;
+            for &size in sizes {
+                bufs.push(IoSlice::new(&data[pos..pos + size]));
+                pos += size;
+            }
+            assert_eq!(blitz_hash_vectored(21, &bufs), oneshot);
+        }
+        assert_eq!(blitz_hash_vectored(21, &[]), blitz_hash(21, b""));
+    }
+
+    #[test]
+    fn test_empty_input_digest_is_stable_and_seeded() {
+        let expected = [
+            (0, "13e6957b056a763e8eae14565fdb525b3fd9147820e776e8d5e1d9543f9c3d5b"),
+            (1, "dcba20587ff4a8cb14b7e74fe096da3aed5637b907a389ce57c03dd567ecad9d"),
+            (42, "d286792564a51a8dbc7243dcc7a2d3e11042beeda86333f0a1b25639f5e74a09"),
+        ];
+        for (seed, digest) in expected {
+            if !cfg!(feature = "legacy-3-rounds") {
+                assert_eq!(hex::encode(blitz_hash(seed, b"")), digest);
+            }
+        }
+
+        let digests: Vec<[u8; 32]> = (0..64).map(|seed| blitz_hash(seed, b"")).collect();
+        for (i, h) in digests.iter().enumerate() {
+            // Length/avalanche mixing must not degenerate with nothing absorbed
+            assert_ne!(h, &[0u8; 32]);
+            assert!(h.iter().any(|&b| b != h[0]));
+            assert!(digest_to_words(h).iter().all(|&w| w != 0));
+            for other in &digests[i + 1..] {
+                assert!(hamming(h, other) > 64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_related_seeds_uncorrelated() {
+        let data: Vec<u8> = (0..1_200_000u32).map(|i| (i * 17 + 9) as u8).collect();
+        let block = &data[..PARALLEL_BLOCK_SIZE];
+        for seed in [0u64, 1, 1000, u64::MAX - 1] {
+            assert_ne!(block_seed(seed, 1), block_seed(seed.wrapping_add(1), 0));
+            assert_ne!(block_seed(seed, 1), block_seed(seed ^ 1, 0));
+            assert_ne!(block_state(seed, 1, block), block_state(seed.wrapping_add(1), 0, block));
+
+            let h1 = blitz_hash_parallel(seed, &data, 4);
+            let h2 = blitz_hash_parallel(seed.wrapping_add(1), &data, 4);
+            assert!(hamming(&h1, &h2) > 80);
+        }
+    }
+
+    #[test]
+    fn test_finalize_to_slice() {
+        let mut state = BlitzState::new(4);
+        state.absorb(b"packet payload");
+        let expected = state.clone().finalize();
+
+        let mut exact = [0u8; 32];
+        state.clone().finalize_to_slice(&mut exact).unwrap();
+        assert_eq!(exact, expected);
+
+        let mut packet = [0xEEu8; 48];
+        state.clone().finalize_to_slice(&mut packet[8..]).unwrap();
+        assert_eq!(&packet[8..40], &expected);
+        assert!(packet[..8].iter().chain(&packet[40..]).all(|&b| b == 0xEE));
+
+        let mut short = [0u8; 31];
+        assert_eq!(
+            state.finalize_to_slice(&mut short),
+            Err(BlitzError::OutputTooSmall { len: 31 })
+        );
+        assert_eq!(short, [0u8; 31]);
+    }
+
+    #[test]
+    fn test_hash64_folds_digest() {
+        for data in [&b""[..], b"a", b"The quick brown fox jumps over the lazy dog"] {
+            let w = digest_to_words(&blitz_hash(8, data));
+            assert_eq!(blitz_hash64(8, data), w[0] ^ w[1] ^ w[2] ^ w[3]);
+        }
+    }
+
+    #[test]
+    fn test_parallel_tiny_inputs_match_serial() {
+        let data: Vec<u8> = (0..33u8).collect();
+        for len in [0, 1, 7, 8, 9, 31, 32, 33] {
+            let tiny = &data[..len];
+            let serial = blitz_hash(17, tiny);
+            // Cutoff 0 forces the block path: one short block, combined on its own
+            let one_block = if len == 0 {
+                serial
+            } else {
+                combine_block_states(17, &[block_state(17, 0, tiny)])
+            };
+            for threads in [1, 2, 8] {
+                let parallel = blitz_hash_parallel(17, tiny, threads);
+                assert_eq!(parallel, serial, "len {} threads {}", len, threads);
+                let forced = blitz_hash_parallel_with(17, tiny, threads, 0);
+                assert_eq!(forced, one_block, "len {} threads {}", len, threads);
+                assert_eq!(blitz_hash_parallel_with(17, tiny, threads, 1), forced);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seed128() {
+        let data = b"hash table key";
+        for seed in [0, 99, u64::MAX] {
+            assert_eq!(blitz_hash_seed128(seed128_from_u64(seed), data), blitz_hash(seed, data));
+        }
+
+        let base = *b"0123456789abcdef";
+        let h0 = blitz_hash_seed128(base, data);
+        if !cfg!(feature = "legacy-3-rounds") {
+            assert_eq!(
+                hex::encode(h0),
+                "a3f74b7449beadecdc5f1e7eb5a59bdb421b6447a6cf1dbca70eaf54ce4ca7cd"
+            );
+        }
+        let mut state = BlitzState::with_seed128(base);
+        state.absorb(data);
+        assert_eq!(state.finalize(), h0);
+
+        for byte in 0..16 {
+            let mut seed = base;
+            seed[byte] ^= 0x01;
+            // Either half reaches every lane, so ~128 bits should move
+            let distance = hamming(&h0, &blitz_hash_seed128(seed, data));
+            assert!((96..=160).contains(&distance), "byte {} moved {} bits", byte, distance);
+        }
+
+        // Swapping the halves is a different key
+        let mut swapped = base;
+        swapped.rotate_left(8);
+        assert_ne!(blitz_hash_seed128(swapped, data), h0);
+    }
+
+    #[test]
+    fn test_empty_absorbs_are_noops() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let mut state = BlitzState::new(6);
+        state.absorb(&[]);
+        for piece in data.chunks(5) {
+            state.absorb(piece);
+            let (pending, total) = (state.buffer_len, state.total_len);
+            state.absorb(&[]);
+            state.absorb(&[]);
+            assert_eq!((state.buffer_len, state.total_len), (pending, total));
+        }
+        assert_eq!(state.finalize(), blitz_hash(6, data));
+    }
+
+    #[test]
+    fn test_framed_fields_are_unambiguous() {
+        let framed = blitz_hash_fields(2, &[b"ab", b"c"]);
+        assert_ne!(framed, blitz_hash_fields(2, &[b"a", b"bc"]));
+        assert_ne!(blitz_hash_fields(2, &[b"abc"]), blitz_hash_fields(2, &[b"abc", b""]));
+
+        let mut plain_1 = BlitzState::new(2);
+        plain_1.absorb(b"ab");
+        plain_1.absorb(b"c");
+        let mut plain_2 = BlitzState::new(2);
+        plain_2.absorb(b"a");
+        plain_2.absorb(b"bc");
+        assert_eq!(plain_1.finalize(), plain_2.finalize());
+
+        let mut state = BlitzState::new(2);
+        state.absorb_framed(b"ab");
+        state.absorb_framed(b"c");
+        assert_eq!(state.finalize(), framed);
+    }
+
+    #[test]
+    fn test_zero_state_still_avalanches() {
+        // Keying with the lane constants zeroes every lane; empty input keeps them zero
+        let zero_key = [K1, K2, K3, K4];
+        let one_shot = blitz_hash_keyed256(zero_key, b"");
+        let streamed = BlitzState::from_state_key(zero_key).finalize();
+        let combined = combine_finish([0; 4]);
+        assert_eq!(one_shot, streamed);
+
+        // The legacy finalize keeps its zero fixed point on purpose
+        if !cfg!(feature = "legacy-3-rounds") {
+            for digest in [one_shot, combined] {
+                for word in digest_to_words(&digest) {
+                    assert_ne!(word, 0);
+                    assert!((16..=48).contains(&word.count_ones()), "weak word {:016x}", word);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_guard_is_injective() {
+        // Pre-avalanche lanes of 0 and K_i used to be mapped to the same value
+        let zero_key = [K1, K2, K3, K4];
+        assert_ne!(blitz_hash_keyed256([0; 4], b""), blitz_hash_keyed256(zero_key, b""));
+        for (i, k) in zero_key.into_iter().enumerate() {
+            let a = digest_to_words(&blitz_hash(k, b""));
+            let b = digest_to_words(&blitz_hash(0, b""));
+            assert_ne!(a[i], b[i]);
+        }
+
+        let words = digest_to_words(&blitz_hash_keyed256([0; 4], b""));
+        for (i, w) in digest_to_words(&blitz_hash_keyed256(zero_key, b"")).into_iter().enumerate() {
+            assert_ne!(w, words[i], "lane {}", i);
+        }
+    }
+
+    #[test]
+    fn test_explain_reports_scalar() {
+        let expected = if cfg!(feature = "portable-simd") {
+            BlitzPath::PortableSimd
+        } else {
+            BlitzPath::Scalar
+        };
+        for data in [&b""[..], b"short", &[0x5Au8; 1000][..]] {
+            let (digest, path) = blitz_hash_explain(3, data);
+            assert_eq!(digest, blitz_hash(3, data));
+            // No intrinsic backend is compiled in
+            assert_eq!(path, expected);
+            assert_eq!(path.as_str(), backend_name());
+            assert_eq!(blitz_hash_auto_explain(3, data), (digest, expected));
+        }
+
+        // The parallel path is reported once the input reaches the cutoff
+        let big = vec![0x5Au8; DEFAULT_MIN_PARALLEL_LEN];
+        let (digest, path) = blitz_hash_auto_explain(3, &big);
+        assert_eq!(path, BlitzPath::Parallel);
+        assert_eq!(digest, blitz_hash_auto(3, &big));
+        assert_ne!(digest, blitz_hash(3, &big));
+        assert_eq!(blitz_hash_auto_explain(3, &big[1..]).1, expected);
+    }
+
+    #[test]
+    fn test_round_modes() {
+        // Pinned digest of the empty input for the mode this build uses
+        let expected = if cfg!(feature = "legacy-3-rounds") {
+            "f82bdfa0ddc0322876e1226532c312dd54d68b8f35ae63c60a7e3fa21696194e"
+        } else {
+            "13e6957b056a763e8eae14565fdb525b3fd9147820e776e8d5e1d9543f9c3d5b"
+        };
+        assert_eq!(hex::encode(blitz_hash(0, b"")), expected);
+
+        for pre in [[1u64, 2, 3, 4], combine_start(0), [u64::MAX; 4]] {
+            let (mut three, mut three_again, mut four) = (pre, pre, pre);
+            avalanche_rounds(&mut three, 3);
+            avalanche_rounds(&mut three_again, 3);
+            avalanche_rounds(&mut four, 4);
+            assert_eq!(three, three_again);
+            assert_ne!(three, four);
+        }
+    }
+
+    #[test]
+    fn test_small_key_path_matches_general() {
+        let data: Vec<u8> = (0..16u8).map(|i| i.wrapping_mul(37) ^ 0xA5).collect();
+        for len in 0..=16 {
+            for seed in [0, 1, 0xDEADBEEF] {
+                let state = combine_start(seed);
+                assert_eq!(hash_small(state, &data[..len]), hash_lanes(state, 0, &data[..len]));
+            }
+        }
+        for len in 0..=8 {
+            let mut padded = [0u8; 8];
+            padded[..len].copy_from_slice(&data[..len]);
+            assert_eq!(read_small(&data[..len]), u64::from_le_bytes(padded));
+        }
+    }
+
+    #[test]
+    fn test_simhash_tracks_similarity() {
+        let words = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel"];
+        let text = |salt: usize| -> Vec<u8> {
+            let picked: Vec<&str> = (0..200).map(|i| words[(i * 7 + salt * (i + 3)) % words.len()]).collect();
+            picked.join(" ").into_bytes()
+        };
+
+        let original = text(0);
+        let mut edited = original.clone();
+        for pos in [10, 400, 900] {
+            edited[pos] ^= 0x20;
+        }
+        let unrelated = text(5);
+
+        let a = blitz_simhash(0, &original, 4);
+        let near = (a ^ blitz_simhash(0, &edited, 4)).count_ones();
+        let far = (a ^ blitz_simhash(0, &unrelated, 4)).count_ones();
+        assert!(near <= 8, "near-duplicate distance {}", near);
+        assert!((20..=44).contains(&far), "unrelated distance {}", far);
+
+        assert_eq!(blitz_simhash(0, &original, 4), a);
+        assert_eq!(blitz_simhash(0, b"ab", 8), blitz_simhash(0, b"ab", 2));
+        assert_eq!(blitz_simhash(0, b"abc", 0), blitz_simhash(0, b"abc", 1));
+        assert_eq!(blitz_simhash(0, b"", 4), blitz_hash64(0, b""));
+    }
+
+    #[test]
+    fn test_streaming_matches_oneshot_all_lengths() {
+        let data: Vec<u8> = (0..256u32).map(|i| (i.wrapping_mul(0x9E37) >> 3) as u8).collect();
+        for seed in [0, 1, 42, u64::MAX] {
+            for len in 0..256 {
+                let oneshot = blitz_hash(seed, &data[..len]);
+                for piece in [1, 3, 7, 8, 31, 32, 33, 256] {
+                    let mut state = BlitzState::new(seed);
+                    for chunk in data[..len].chunks(piece) {
+                        state.absorb(chunk);
+                    }
+                    assert_eq!(state.finalize(), oneshot, "seed {} len {} piece {}", seed, len, piece);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_loop_bounds_near_usize_max() {
+        // Can't allocate these, but the bound checks only see lengths and offsets
+        let len = usize::MAX;
+        for n in [8, 32, 64] {
+            // `pos + n` would overflow here and wrongly allow (or panic on) another chunk
+            assert!(!has_remaining(len, len - n + 1, n));
+            assert!(has_remaining(len, len - n, n));
+            assert!(!has_remaining(len, len, n));
+        }
+        assert!(has_remaining(len, 0, 64));
+        assert!(!has_remaining(0, 0, 8));
+    }
+
+    #[test]
+    fn test_endian_output() {
+        let fox = b"The quick brown fox jumps over the lazy dog";
+        let little = blitz_hash_with_endian(42, fox, Endian::Little);
+        let big = blitz_hash_with_endian(42, fox, Endian::Big);
+        assert_eq!(little, blitz_hash(42, fox));
+        if !cfg!(feature = "legacy-3-rounds") {
+            assert_eq!(
+                hex::encode(little),
+                "e857e2e456e9b1e8f62096da3aae9d25ee7d1a6a81ed0e53c074ff042fc7dcfc"
+            );
+        }
+
+        for (le, be) in little.chunks(8).zip(big.chunks(8)) {
+            let mut reversed = le.to_vec();
+            reversed.reverse();
+            assert_eq!(be, &reversed[..]);
+            assert_eq!(
+                u64::from_le_bytes(le.try_into().unwrap()),
+                u64::from_be_bytes(be.try_into().unwrap())
+            );
+        }
+
+        let mut state = BlitzState::new(42);
+        state.absorb(fox);
+        assert_eq!(state.clone().finalize_with_endian(Endian::Big), big);
+        assert_eq!(state.finalize_with_endian(Endian::default()), little);
+    }
+
+    #[test]
+    fn test_finalized_blitz() {
+        let mut state = BlitzState::new(42);
+        state.absorb(b"The quick brown fox jumps over the lazy dog");
+        let raw = state.clone().finalize();
+        let finalized = state.into_finalized();
+
+        assert_eq!(finalized.bytes(), &raw);
+        assert_eq!(finalized.hex(), hex::encode(raw));
+        assert_eq!(finalized.hex().len(), 64);
+        assert!(finalized.hex().bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)));
+
+        assert!(finalized.verify(&raw));
+        let mut wrong = raw;
+        wrong[31] ^= 1;
+        assert!(!finalized.verify(&wrong));
+        assert!(!finalized.verify(&raw[..31]));
+        assert_eq!(FinalizedBlitz::from(BlitzState::new(0)).bytes(), &blitz_hash(0, b""));
+    }
+
+    #[test]
+    fn test_blitz_digest_as_map_key() {
+        use std::collections::HashMap;
+
+        let keys: Vec<BlitzDigest> = (0u8..16).map(|i| blitz_digest(7, &[i; 40])).collect();
+        let mut map = HashMap::new();
+        for (i, key) in keys.iter().enumerate() {
+            map.insert(*key, i);
+        }
+        assert_eq!(map.len(), keys.len());
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(map[key], i);
+        }
+
+        // A parsed digest is the same key as the computed one, whatever the hex case
+        let parsed: BlitzDigest = keys[3].to_string().to_uppercase().parse().unwrap();
+        assert_eq!(parsed, keys[3]);
+        assert_eq!(map.get(&parsed), Some(&3));
+        assert_eq!(*parsed.bytes(), blitz_hash(7, &[3; 40]));
+
+        assert_eq!("abc".parse::<BlitzDigest>(), Err(BlitzError::InvalidHex));
+        assert_eq!("zz".repeat(32).parse::<BlitzDigest>(), Err(BlitzError::InvalidHex));
+    }
+
+    #[test]
+    fn test_merge_matches_parallel() {
+        let seed = 42;
+        let data: Vec<u8> = (0..4 * PARALLEL_BLOCK_SIZE).map(|i| (i * 31 % 251) as u8).collect();
+
+        // Four producers fill their quarters out of order, in uneven pieces
+        let mut states: Vec<BlitzState> = (0..4).map(|i| BlitzState::substream(seed, i)).collect();
+        for i in [2, 0, 3, 1] {
+            let quarter = &data[i * PARALLEL_BLOCK_SIZE..(i + 1) * PARALLEL_BLOCK_SIZE];
+            for piece in quarter.chunks(1000 + i * 7) {
+                states[i].absorb(piece);
+            }
+        }
+
+        let merged = BlitzState::merge(seed, &states);
+        assert_eq!(merged, blitz_hash_parallel_with(seed, &data, 4, 0));
+        assert_eq!(merged, blitz_hash_parallel_with(seed, &data, 1, 0));
+
+        // The merge is not the digest of the concatenation, and order matters
+        assert_ne!(merged, blitz_hash(seed, &data));
+        states.swap(0, 1);
+        assert_ne!(BlitzState::merge(seed, &states), merged);
+    }
+
+    #[cfg(all(feature = "debug-integrity", debug_assertions))]
+    #[test]
+    fn test_integrity_check_passes() {
+        let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 256) as u8).collect();
+        for piece_len in [1, 5, 31, 32, 33, 100, 1000] {
+            let mut a = BlitzState::new(3);
+            let mut b = BlitzState::new(3);
+            for piece in data.chunks(piece_len) {
+                a.absorb(piece);
+                b.absorb(piece);
+            }
+            assert_eq!(a.integrity_hash64(), b.integrity_hash64());
+            assert_eq!(a.finalize(), blitz_hash(3, &data));
+        }
+
+        // The same bytes from a buffer that changed between calls are caught
+        let mut a = BlitzState::new(3);
+        let mut b = BlitzState::new(3);
+        a.absorb(b"abc");
+        b.absorb(b"abd");
+        assert_ne!(a.integrity_hash64(), b.integrity_hash64());
+    }
+
+    #[cfg(all(feature = "debug-integrity", debug_assertions))]
+    #[test]
+    #[should_panic(expected = "last bytes absorbed")]
+    fn test_integrity_check_catches_buffer_corruption() {
+        let mut state = BlitzState::new(0);
+        state.absorb(b"hello");
+        state.buffer[0] ^= 1;
+        let _ = state.finalize();
+    }
+
+    #[test]
+    fn test_blitz_hash_profiles() {
+        let profiles = [BlitzProfile::Fast, BlitzProfile::Balanced, BlitzProfile::Strong];
+        for len in [0, 1, 7, 8, 16, 17, 31, 32, 33, 100, 1000] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 13 % 256) as u8).collect();
+            assert_eq!(blitz_hash_profile(BlitzProfile::Balanced, 5, &data), blitz_hash(5, &data));
+
+            let digests = profiles.map(|p| blitz_hash_profile(p, 5, &data));
+            for (p, digest) in profiles.iter().zip(&digests) {
+                assert_eq!(blitz_hash_profile(*p, 5, &data), *digest);
+                assert_ne!(blitz_hash_profile(*p, 6, &data), *digest);
+            }
+            assert_ne!(digests[0], digests[1], "len {}", len);
+            assert_ne!(digests[1], digests[2], "len {}", len);
+            assert_ne!(digests[0], digests[2], "len {}", len);
+        }
+        assert_eq!(BlitzProfile::default(), BlitzProfile::Balanced);
+    }
+
+    #[test]
+    fn test_counted_and_total_absorbed() {
+        for len in [0, 1, 16, 17, 32, 1000] {
+            let data = vec![0xabu8; len];
+            assert_eq!(blitz_hash_counted(9, &data), (blitz_hash(9, &data), len));
+        }
+
+        let mut state = BlitzState::new(9);
+        assert_eq!(state.total_absorbed(), 0);
+        let mut expected = 0;
+        for piece_len in [0, 5, 27, 32, 1, 100] {
+            state.absorb(&vec![1u8; piece_len]);
+            expected += piece_len as u64;
+            assert_eq!(state.total_absorbed(), expected);
+        }
+        assert_eq!(state.finalize(), blitz_hash(9, &[1u8; 165]));
+    }
+
+    #[test]
+    fn test_digest_word_conversions() {
+        for len in [0, 5, 64] {
+            let digest = blitz_hash(1, &vec![9u8; len]);
+            assert_eq!(words_to_digest(&digest_to_words(&digest)), digest);
+            let typed = BlitzDigest(digest);
+            assert_eq!(BlitzDigest::from_words(typed.to_words()), typed);
+        }
+        let words = [0, 1, u64::MAX, 0x0123_4567_89ab_cdef];
+        assert_eq!(digest_to_words(&words_to_digest(&words)), words);
+
+        if !cfg!(feature = "legacy-3-rounds") {
+            let fox = blitz_digest(42, b"The quick brown fox jumps over the lazy dog");
+            assert_eq!(
+                fox.to_words(),
+                [0xe8b1_e956_e4e2_57e8, 0x259d_ae3a_da96_20f6, 0x530e_ed81_6a1a_7dee, 0xfcdc_c72f_04ff_74c0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_extend_matches_absorb() {
+        let mut s = BlitzState::new(0);
+        s.extend(b"hello".iter().copied());
+        assert_eq!(s.finalize(), blitz_hash(0, b"hello"));
+
+        let data: Vec<u8> = (0..200u32).map(|i| (i * 17 % 256) as u8).collect();
+        let mut bytes = BlitzState::new(4);
+        bytes.extend(data.iter().copied());
+        let mut pieces = BlitzState::new(4);
+        pieces.extend(data.chunks(7));
+        let mut mixed = BlitzState::new(4);
+        mixed.absorb(&data[..45]);
+        mixed.absorb_byte(data[45]);
+        mixed.extend(data[46..].iter().copied());
+
+        let expected = blitz_hash(4, &data);
+        assert_eq!(bytes.total_absorbed(), 200);
+        assert_eq!(bytes.finalize(), expected);
+        assert_eq!(pieces.finalize(), expected);
+        assert_eq!(mixed.finalize(), expected);
+    }
+
+    #[test]
+    fn test_parallel_pathological_thread_counts() {
+        for threads in [0, 1, 8, 1000, usize::MAX] {
+            assert_eq!(blitz_hash_parallel(3, &[], threads), blitz_hash(3, b""));
+            assert_eq!(blitz_hash_parallel_with(3, &[], threads, 0), blitz_hash(3, b""));
+            let reader = blitz_hash_parallel_reader_with(3, &[][..], threads, 0).unwrap();
+            assert_eq!(reader, blitz_hash(3, b""));
+        }
+
+        // Fewer bytes (and blocks) than threads, with the cutoff forced down to 0
+        for len in [1, 3, 63, PARALLEL_BLOCK_SIZE + 1] {
+            let data = vec![0x33u8; len];
+            let serial = blitz_hash_parallel_with(3, &data, 1, 0);
+            for threads in [2, 64, 1000, usize::MAX] {
+                assert_eq!(blitz_hash_parallel_with(3, &data, threads, 0), serial);
+            }
+            assert_eq!(blitz_hash_parallel(3, &data, 64), blitz_hash(3, &data));
+        }
+    }
+
+    #[test]
+    fn test_blitz_hash_hex() {
+        for (seed, data) in [(0, &b""[..]), (42, b"The quick brown fox jumps over the lazy dog")] {
+            let text = blitz_hash_hex(seed, data);
+            assert_eq!(text, hex::encode(blitz_hash(seed, data)));
+            assert_eq!(text.len(), 64);
+        }
+        if !cfg!(feature = "legacy-3-rounds") {
+            assert_eq!(
+                blitz_hash_hex(42, b"The quick brown fox jumps over the lazy dog"),
+                "e857e2e456e9b1e8f62096da3aae9d25ee7d1a6a81ed0e53c074ff042fc7dcfc"
+            );
+        }
+        for len in 0..40 {
+            let text = blitz_hash_hex(len as u64, &vec![0xffu8; len]);
+            assert_eq!(text.len(), 64);
+            assert!(text.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)));
+        }
+    }
+
+    #[test]
+    fn test_streaming_64bit_matches_blitz_hash64() {
+        let message: Vec<u8> = (0..500u32).map(|i| (i * 29 % 256) as u8).collect();
+        for piece_len in [1, 7, 32, 33, 500] {
+            let mut state = BlitzState::new(11);
+            for (i, piece) in message.chunks(piece_len).enumerate() {
+                state.absorb(piece);
+                let so_far = ((i + 1) * piece_len).min(message.len());
+                assert_eq!(state.finish64(), blitz_hash64(11, &message[..so_far]));
+            }
+            assert_eq!(state.finalize64(), blitz_hash64(11, &message));
+        }
+        assert_eq!(BlitzState::new(0).finalize64(), blitz_hash64(0, b""));
+    }
+
+    #[test]
+    fn test_pending_bytes() {
+        let mut state = BlitzState::new(0);
+        state.absorb(&[1u8; 13]);
+        // The buffer holds a whole 32-byte block, so nothing is folded yet
+        assert_eq!(state.pending_bytes(), 13);
+        assert_eq!(state.bytes_absorbed(), 13);
+
+        state.absorb(&[2u8; 24]);
+        assert_eq!(state.pending_bytes(), 5);
+        assert_eq!(state.bytes_absorbed(), 37);
+        assert_eq!(state.snapshot().absorbed(), 37);
+
+        state.absorb(&[3u8; 27]);
+        assert_eq!(state.pending_bytes(), 0);
+        assert_eq!(state.bytes_absorbed(), state.total_absorbed());
+    }
+
+    #[test]
+    fn test_single_byte_inputs_are_far_apart() {
+        // Random 256-bit digests differ in 128 +- 8 bits; 80..=176 is a 6-sigma band
+        let digests: Vec<[u8; 32]> = (0..=255u8).map(|b| blitz_hash(0, &[b])).collect();
+        let mut total = 0u64;
+        let mut pairs = 0u64;
+        for i in 0..digests.len() {
+            for j in i + 1..digests.len() {
+                let d = hamming(&digests[i], &digests[j]);
+                assert!((80..=176).contains(&d), "bytes {} and {}: {} bits apart", i, j, d);
+                total += d as u64;
+                pairs += 1;
+            }
+        }
+        let mean = total as f64 / pairs as f64;
+        assert!((127.0..=129.0).contains(&mean), "mean distance {}", mean);
+
+        // Same byte, neighbouring seeds
+        for b in 0..=255u8 {
+            for seed in 1..8 {
+                let d = hamming(&digests[b as usize], &blitz_hash(seed, &[b]));
+                assert!((80..=176).contains(&d), "byte {} seed {}: {} bits apart", b, seed, d);
+            }
+        }
+    }
+
+    #[test]
+    fn test_clone_forks_mid_stream() {
+        let data: Vec<u8> = (0..300u32).map(|i| (i * 7 % 256) as u8).collect();
+        // Prefixes leave the buffer empty, partially filled, or one byte short of a block
+        for prefix_len in [0, 5, 13, 32, 45, 63, 100] {
+            let (prefix, rest) = data.split_at(prefix_len);
+            let mut base = BlitzState::new(8);
+            base.absorb(prefix);
+            assert_eq!(base.pending_bytes(), prefix_len % 32);
+
+            for suffix_len in [0, 1, 19, 32, 77] {
+                let suffix = &rest[..suffix_len];
+                let mut fork = base.clone();
+                fork.absorb(suffix);
+                let whole = [prefix, suffix].concat();
+                assert_eq!(fork.finalize(), blitz_hash(8, &whole));
+            }
+            // Forking leaves the original untouched
+            assert_eq!(base.finalize(), blitz_hash(8, prefix));
+        }
+    }
+
+    #[cfg(all(feature = "rayon", not(feature = "std-threads")))]
+    #[test]
+    fn test_blitz_parallel_custom_pool() {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let hasher = BlitzParallel::with_pool(pool);
+        assert_eq!(hasher.pool().current_num_threads(), 2);
+
+        let data: Vec<u8> = (0..3_000_000u32).map(|i| (i % 253) as u8).collect();
+        let expected = blitz_hash_parallel(21, &data, 8);
+        assert_eq!(hasher.hash(21, &data), expected);
+        assert_eq!(hasher.hash(21, &data), expected);
+        assert_eq!(hasher.hash(21, b"short"), blitz_hash(21, b"short"));
+    }
+
+    #[test]
+    fn test_blitz_hash_auto() {
+        assert!(auto_threads() >= 1);
+
+        for data in [&b""[..], b"small", &[9u8; 4096][..]] {
+            assert_eq!(blitz_hash_auto(2, data), blitz_hash(2, data));
+        }
+
+        // Above the cutoff the digest is the parallel one, whatever the core count
+        let large = vec![0x5au8; DEFAULT_MIN_PARALLEL_LEN + 12345];
+        let parallel = blitz_hash_parallel(2, &large, 1);
+        assert_eq!(blitz_hash_auto(2, &large), parallel);
+        assert_ne!(parallel, blitz_hash(2, &large));
+
+        let medium = vec![0x5au8; 3 * PARALLEL_BLOCK_SIZE];
+        assert_eq!(blitz_hash_auto_with(2, &medium, 1024), blitz_hash_parallel_with(2, &medium, 1, 1024));
+        assert_eq!(blitz_hash_auto_with(2, &medium, usize::MAX), blitz_hash(2, &medium));
+    }
+
+    #[test]
+    fn test_blitz_hash_truncated() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let full = blitz_hash(42, data);
+        assert_eq!(blitz_hash_truncated(42, data, 32).unwrap(), full.to_vec());
+
+        let lengths = [1, 4, 8, 16, 20, 31];
+        for &len in &lengths {
+            let out = blitz_hash_truncated(42, data, len).unwrap();
+            assert_eq!(out.len(), len);
+            assert_eq!(out, blitz_hash_truncated(42, data, len).unwrap());
+            assert_ne!(out, blitz_hash_truncated(43, data, len).unwrap());
+            // Not a prefix of the full digest, nor of any other truncation
+            if len >= 4 {
+                assert_ne!(out[..], full[..len]);
+                for &other in lengths.iter().filter(|&&o| o > len) {
+                    assert_ne!(out[..], blitz_hash_truncated(42, data, other).unwrap()[..len]);
+                }
+            }
+        }
+        // The 16-byte output is about as far from the digest's prefix as a random one (~64 bits)
+        let short = blitz_hash_truncated(42, data, 16).unwrap();
+        let distance: u32 = short.iter().zip(&full).map(|(a, b)| (a ^ b).count_ones()).sum();
+        assert!(distance > 32, "{} bits", distance);
+    }
+
+    #[test]
+    fn test_blitz_hash_truncated_rejects_bad_lengths() {
+        for len in [0, 33, usize::MAX] {
+            assert_eq!(blitz_hash_truncated(0, b"x", len), Err(BlitzError::InvalidOutputLength { len }));
+        }
+    }
+
+    #[test]
+    fn test_lane_states_for_abc() {
+        // Reference lanes for porters: seed 0, message b"abc"
+        let mut state = BlitzState::new(0);
+        assert_eq!(state.state_words(), [K1, K2, K3, K4]);
+        state.absorb(b"abc");
+        // Under one block, so still only buffered
+        assert_eq!(state.state_words(), [K1, K2, K3, K4]);
+
+        // Tail 0x636261 into every lane (rotl 0/13/27/43), then length 3 and the avalanche
+        let mut lanes = state.state_words();
+        mix_tail(&mut lanes, 0x0063_6261);
+        assert_eq!(
+            lanes,
+            [0xda04_7dd4_44b3_213e, 0x407f_6bfc_5597_026b, 0xe3ac_055f_d615_6a9e, 0xcb0a_9344_8fe5_ea12]
+        );
+        let digest = state.finalize();
+        if !cfg!(feature = "legacy-3-rounds") {
+            assert_eq!(
+                digest_to_words(&digest),
+                [0xdd0a_addd_3f3f_b3c8, 0xb080_b4c7_d07d_cca2, 0xd18e_4cae_4421_83ef, 0xd9b9_961e_811f_13c3]
+            );
+        }
+
+        // A whole block is folded as soon as it's complete
+        let mut block = BlitzState::new(0);
+        block.absorb(&b"abc".repeat(11)[..32]);
+        assert_eq!(
+            block.state_words(),
+            [0xb580_8be2_4f3c_4578, 0x646e_b3d8_2521_e4c9, 0x6660_856d_b068_6a71, 0x5efa_da51_b55d_03c1]
+        );
+    }
+
+    #[test]
+    fn test_nonce_finalize() {
+        let data = b"message body that repeats across calls";
+        let d0 = blitz_hash_nonce(1, 0, data);
+        let d1 = blitz_hash_nonce(1, 1, data);
+        assert_ne!(d0, d1);
+        assert!(hamming(&d0, &d1) > 80, "nonces 0 and 1 only {} bits apart", hamming(&d0, &d1));
+        assert_ne!(d0, blitz_hash(1, data));
+        assert_eq!(blitz_hash_nonce(1, 0, data), d0);
+
+        // Each single-bit nonce change moves the digest
+        for bit in 0..64 {
+            let flipped = blitz_hash_nonce(1, 1 << bit, data);
+            assert!(hamming(&d0, &flipped) > 80, "nonce bit {}", bit);
+        }
+
+        // Streaming matches one-shot for every split, empty input included
+        for len in [0, 3, 32, 38] {
+            let mut state = BlitzState::new(1);
+            for piece in data[..len].chunks(5) {
+                state.absorb(piece);
+            }
+            assert_eq!(state.finalize_with_nonce(99), blitz_hash_nonce(1, 99, &data[..len]));
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Python bindings - `python` feature
+//! Digests are byte-identical to the Rust `blitz_hash` / `BlitzState`
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::BlitzState;
+
+/// blitz_hash(data: bytes, seed: int = 0) -> bytes
+#[pyfunction]
+#[pyo3(name = "blitz_hash", signature = (data, seed = 0))]
+fn py_blitz_hash<'py>(py: Python<'py>, data: &[u8], seed: u64) -> Bound<'py, PyBytes> {
+    // Large buffers shouldn't hold the GIL
+    let digest = py.allow_threads(|| crate::blitz_hash(seed, data));
+    PyBytes::new_bound(py, &digest)
+}
+
+/// Incremental hasher - `update` any number of times, then `digest`/`hexdigest`
+#[pyclass(name = "BlitzHasher")]
+struct PyBlitzHasher {
+    state: BlitzState,
+}
+
+#[pymethods]
+impl PyBlitzHasher {
+    #[new]
+    #[pyo3(signature = (seed = 0))]
+    fn new(seed: u64) -> Self {
+        Self {
+            state: BlitzState::new(seed),
+        }
+    }
+
+    fn update(&mut self, py: Python<'_>, data: &[u8]) {
+        let state = &mut self.state;
+        py.allow_threads(|| state.absorb(data));
+    }
+
+    /// Digest so far - the hasher can keep absorbing afterwards
+    fn digest<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new_bound(py, &self.state.clone().finalize())
+    }
+
+    fn hexdigest(&self) -> String {
+        hex::encode(self.state.clone().finalize())
+    }
+}
+
+#[pymodule]
+fn blitzhash(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(py_blitz_hash, m)?)?;
+    m.add_class::<PyBlitzHasher>()?;
+    Ok(())
+}
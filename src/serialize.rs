@@ -0,0 +1,476 @@
+//! Hashing of `Serialize` values - `serde` feature
+//! A small fixed binary encoding streamed straight into `BlitzState`, no intermediate buffer
+//!
+//! Encoding (all integers little-endian, lengths as u64):
+//! - bool: 1 byte; integers: their full width; floats: `to_bits`; char: u32
+//! - str / bytes: length, then the bytes
+//! - None: `0`; Some(v): `1`, then v
+//! - unit, unit struct: nothing; newtype struct: the inner value
+//! - enum variants: u32 variant index, then the payload
+//! - seq: length, then the elements; tuples and structs: the fields in order, no names
+//! - map: length, then the encoded entries sorted by their bytes, so iteration order
+//!   (e.g. a `HashMap`'s random one) never reaches the digest
+
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+use crate::{BlitzError, BlitzState};
+
+/// Hash `value` through the stable binary encoding above
+///
+/// The same logical value gives the same digest on every run and platform. Map
+/// entry order doesn't matter; sequence order does - including `HashSet`, which
+/// serializes as a sequence in its random order, so hash a `BTreeSet` instead.
+/// Struct fields are hashed in declaration order without their names.
+///
+/// Fails with `BlitzError::Unserializable` for sequences of unknown length or when
+/// the value's `Serialize` impl itself reports an error.
+pub fn blitz_hash_serde<T: Serialize + ?Sized>(seed: u64, value: &T) -> Result<[u8; 32], BlitzError> {
+    let mut state = BlitzState::new(seed);
+    value
+        .serialize(Encoder { out: &mut state })
+        .map_err(|_| BlitzError::Unserializable)?;
+    Ok(state.finalize())
+}
+
+/// Where encoded bytes go - the hasher itself, or a scratch buffer for a map entry
+trait Sink {
+    fn put(&mut self, bytes: &[u8]);
+}
+
+impl Sink for BlitzState {
+    fn put(&mut self, bytes: &[u8]) {
+        self.absorb(bytes);
+    }
+}
+
+impl Sink for Vec<u8> {
+    fn put(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+#[derive(Debug)]
+struct EncodeError(String);
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+impl ser::Error for EncodeError {
+    fn custom<M: fmt::Display>(msg: M) -> Self {
+        EncodeError(msg.to_string())
+    }
+}
+
+struct Encoder<'a, S: Sink> {
+    out: &'a mut S,
+}
+
+impl<S: Sink> Encoder<'_, S> {
+    fn len(&mut self, len: usize) {
+        self.out.put(&(len as u64).to_le_bytes());
+    }
+
+    fn variant(&mut self, index: u32) {
+        self.out.put(&index.to_le_bytes());
+    }
+}
+
+impl<'a, S: Sink> ser::Serializer for Encoder<'a, S> {
+    type Ok = ();
+    type Error = EncodeError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = MapEncoder<'a, S>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), EncodeError> {
+        self.out.put(&[v as u8]);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), EncodeError> {
+        self.out.put(&[v]);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), EncodeError> {
+        self.out.put(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), EncodeError> {
+        self.out.put(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), EncodeError> {
+        self.out.put(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), EncodeError> {
+        self.out.put(&(v as u32).to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_str(mut self, v: &str) -> Result<(), EncodeError> {
+        self.len(v.len());
+        self.out.put(v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_bytes(mut self, v: &[u8]) -> Result<(), EncodeError> {
+        self.len(v.len());
+        self.out.put(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), EncodeError> {
+        self.out.put(&[0]);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), EncodeError> {
+        self.out.put(&[1]);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        mut self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+    ) -> Result<(), EncodeError> {
+        self.variant(index);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        mut self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError> {
+        self.variant(index);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(mut self, len: Option<usize>) -> Result<Self, EncodeError> {
+        let len = len.ok_or_else(|| EncodeError("sequence length must be known up front".into()))?;
+        self.len(len);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, EncodeError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, EncodeError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        mut self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, EncodeError> {
+        self.variant(index);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapEncoder<'a, S>, EncodeError> {
+        Ok(MapEncoder {
+            out: self.out,
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            current: Vec::new(),
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, EncodeError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        mut self,
+        _name: &'static str,
+        index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, EncodeError> {
+        self.variant(index);
+        Ok(self)
+    }
+}
+
+impl<S: Sink> ser::SerializeSeq for Encoder<'_, S> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodeError> {
+        value.serialize(Encoder { out: &mut *self.out })
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeTuple for Encoder<'_, S> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodeError> {
+        value.serialize(Encoder { out: &mut *self.out })
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeTupleStruct for Encoder<'_, S> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodeError> {
+        value.serialize(Encoder { out: &mut *self.out })
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeTupleVariant for Encoder<'_, S> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodeError> {
+        value.serialize(Encoder { out: &mut *self.out })
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeStruct for Encoder<'_, S> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError> {
+        value.serialize(Encoder { out: &mut *self.out })
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl<S: Sink> ser::SerializeStructVariant for Encoder<'_, S> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), EncodeError> {
+        value.serialize(Encoder { out: &mut *self.out })
+    }
+
+    fn end(self) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+/// Encodes each entry on the side, then writes them sorted - map order never matters
+struct MapEncoder<'a, S: Sink> {
+    out: &'a mut S,
+    entries: Vec<Vec<u8>>,
+    current: Vec<u8>,
+}
+
+impl<S: Sink> ser::SerializeMap for MapEncoder<'_, S> {
+    type Ok = ();
+    type Error = EncodeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), EncodeError> {
+        key.serialize(Encoder { out: &mut self.current })
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), EncodeError> {
+        value.serialize(Encoder { out: &mut self.current })?;
+        self.entries.push(std::mem::take(&mut self.current));
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<(), EncodeError> {
+        self.entries.sort_unstable();
+        self.out.put(&(self.entries.len() as u64).to_le_bytes());
+        for entry in &self.entries {
+            self.out.put(entry);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blitz_hash;
+    use serde::Serialize;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Serialize)]
+    enum Kind {
+        Plain,
+        Tagged(u16),
+    }
+
+    #[derive(Serialize)]
+    struct Inner {
+        name: String,
+        weights: Vec<f64>,
+        kind: Kind,
+    }
+
+    #[derive(Serialize)]
+    struct Outer {
+        id: u64,
+        parent: Option<u32>,
+        children: Vec<Inner>,
+        labels: HashMap<String, i32>,
+        ordered: BTreeMap<u8, bool>,
+    }
+
+    fn sample(label_order: &[(&str, i32)]) -> Outer {
+        Outer {
+            id: 7,
+            parent: Some(3),
+            children: vec![
+                Inner { name: "a".into(), weights: vec![0.5, -1.0], kind: Kind::Plain },
+                Inner { name: "bc".into(), weights: vec![], kind: Kind::Tagged(9) },
+            ],
+            labels: label_order.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            ordered: [(1, true), (0, false)].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn test_serde_hash_is_stable() {
+        let labels = [("x", 1), ("y", -2), ("zz", 30), ("w", 4)];
+        let reference = blitz_hash_serde(5, &sample(&labels)).unwrap();
+        assert_eq!(blitz_hash_serde(5, &sample(&labels)).unwrap(), reference);
+
+        // Map insertion (and HashMap iteration) order doesn't reach the digest
+        let mut reversed = labels;
+        reversed.reverse();
+        for _ in 0..8 {
+            assert_eq!(blitz_hash_serde(5, &sample(&reversed)).unwrap(), reference);
+        }
+
+        let mut changed = sample(&labels);
+        changed.labels.insert("x".into(), 2);
+        assert_ne!(blitz_hash_serde(5, &changed).unwrap(), reference);
+        assert_ne!(blitz_hash_serde(6, &sample(&labels)).unwrap(), reference);
+    }
+
+    #[test]
+    fn test_serde_encoding_is_pinned() {
+        // (u32, &str, Option<u8>) -> 01000000 | 0200000000000000 "ab" | 01 ff
+        let mut expected = vec![1, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, b'a', b'b', 1, 0xff];
+        assert_eq!(blitz_hash_serde(0, &(1u32, "ab", Some(0xffu8))).unwrap(), blitz_hash(0, &expected));
+
+        // Field boundaries are length-framed: ("ab", "c") != ("a", "bc")
+        assert_ne!(blitz_hash_serde(0, &("ab", "c")).unwrap(), blitz_hash_serde(0, &("a", "bc")).unwrap());
+
+        expected.clear();
+        assert_eq!(blitz_hash_serde(0, &()).unwrap(), blitz_hash(0, &expected));
+    }
+
+    #[test]
+    fn test_serde_unknown_length_seq_fails() {
+        struct Unsized;
+        impl Serialize for Unsized {
+            fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use ser::SerializeSeq;
+                let seq = serializer.serialize_seq(None)?;
+                seq.end()
+            }
+        }
+        assert_eq!(blitz_hash_serde(0, &Unsized), Err(BlitzError::Unserializable));
+    }
+}
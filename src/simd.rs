@@ -0,0 +1,78 @@
+//! `std::simd` block loop - `portable-simd` feature, nightly only
+//! The four lanes are one `u64x4`; digests are identical to the scalar loop
+
+use std::simd::u64x4;
+
+use crate::{has_remaining, K1, K2, K3, K4};
+
+/// `mix_chunk` on all four lanes at once
+#[inline(always)]
+fn mix_chunk_x4(mut h: u64x4, chunk: u64x4, k: u64x4) -> u64x4 {
+    h ^= chunk;
+    h *= k;
+    h ^= rotate_right(h, 27);
+    h *= u64x4::splat(K1);
+    h ^= rotate_right(h, 31);
+    h
+}
+
+#[inline(always)]
+fn rotate_right(h: u64x4, n: u64) -> u64x4 {
+    (h >> u64x4::splat(n)) | (h << u64x4::splat(64 - n))
+}
+
+/// Whole 32-byte blocks, word `i` into lane `i` - returns the offset of the remainder
+#[inline(always)]
+pub(crate) fn fold_blocks(state: &mut [u64; 4], data: &[u8]) -> usize {
+    let k = u64x4::from_array([K1, K2, K3, K4]);
+    let mut h = u64x4::from_array(*state);
+
+    let mut pos = 0;
+    while has_remaining(data.len(), pos, 32) {
+        let block = &data[pos..pos + 32];
+        let words = std::array::from_fn(|i| {
+            u64::from_le_bytes(block[i * 8..i * 8 + 8].try_into().unwrap())
+        });
+        trace_lanes!(format!("block @{} in", pos), h.to_array());
+        h = mix_chunk_x4(h, u64x4::from_array(words), k);
+        trace_lanes!(format!("block @{} out", pos), h.to_array());
+        pos += 32;
+    }
+
+    *state = h.to_array();
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blitz_hash, fold_blocks_scalar};
+
+    #[test]
+    fn test_simd_matches_scalar_blocks() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2654435761) >> 13) as u8).collect();
+        for len in [0, 31, 32, 33, 64, 100, 1000, 4096] {
+            let mut simd = [1, 2, 3, 4];
+            let mut scalar = simd;
+            let simd_pos = fold_blocks(&mut simd, &data[..len]);
+            let scalar_pos = fold_blocks_scalar(&mut scalar, &data[..len]);
+            assert_eq!(simd_pos, scalar_pos);
+            assert_eq!(simd, scalar, "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_simd_matches_scalar_vectors() {
+        // Scalar reference digests, computed before the SIMD loop existed
+        if cfg!(feature = "legacy-3-rounds") {
+            return;
+        }
+        let fox = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            hex::encode(blitz_hash(42, fox)),
+            "e857e2e456e9b1e8f62096da3aae9d25ee7d1a6a81ed0e53c074ff042fc7dcfc"
+        );
+        assert!(hex::encode(blitz_hash(0, b"")).starts_with("13e6957b"));
+        assert!(hex::encode(blitz_hash(0, b"Hello, world!")).starts_with("6ad182d13ceab0b7"));
+    }
+}
@@ -0,0 +1,68 @@
+//! Output quality estimators - `stats` feature
+//! Quick numeric checks for comparing algorithm tweaks, not a substitute for SMHasher
+
+use crate::blitz_hash;
+
+/// Longest random input `estimate_bias` generates
+const MAX_SAMPLE_LEN: usize = 64;
+
+/// Mean absolute deviation of each output bit's one-probability from 0.5
+///
+/// Hashes `samples` pseudo-random inputs of 0..=64 bytes under `seed`. An ideal
+/// hash scores about `0.4 / sqrt(samples)`; larger values mean biased output bits.
+/// The input stream is derived from `seed`, so results are reproducible.
+pub fn estimate_bias(seed: u64, samples: usize) -> f64 {
+    if samples == 0 {
+        return 0.0;
+    }
+
+    let mut rng = SplitMix64(seed);
+    let mut ones = [0u64; 256];
+    let mut input = [0u8; MAX_SAMPLE_LEN];
+
+    for _ in 0..samples {
+        let len = (rng.next() % (MAX_SAMPLE_LEN as u64 + 1)) as usize;
+        for chunk in input[..len].chunks_mut(8) {
+            chunk.copy_from_slice(&rng.next().to_le_bytes()[..chunk.len()]);
+        }
+
+        let digest = blitz_hash(seed, &input[..len]);
+        for (bit, count) in ones.iter_mut().enumerate() {
+            *count += ((digest[bit / 8] >> (bit % 8)) & 1) as u64;
+        }
+    }
+
+    let n = samples as f64;
+    ones.iter().map(|&c| (c as f64 / n - 0.5).abs()).sum::<f64>() / 256.0
+}
+
+/// Tiny deterministic PRNG for sample inputs (not secure)
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bias_is_small() {
+        let bias = estimate_bias(0, 100_000);
+        assert!(bias < 0.02, "output bit bias {:.5}", bias);
+        assert!(bias > 0.0);
+    }
+
+    #[test]
+    fn test_bias_is_reproducible() {
+        assert_eq!(estimate_bias(7, 1000), estimate_bias(7, 1000));
+        assert_eq!(estimate_bias(7, 0), 0.0);
+    }
+}
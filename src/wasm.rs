@@ -0,0 +1,12 @@
+//! JavaScript entry points - `wasm` feature
+//! Build with `wasm-pack build --no-default-features --features wasm`
+
+use wasm_bindgen::prelude::*;
+
+/// 32-byte digest of `data`, byte-identical to the native `blitz_hash`
+///
+/// `seed` is a JS `BigInt` (u64).
+#[wasm_bindgen]
+pub fn blitz_hash_wasm(seed: u64, data: &[u8]) -> Vec<u8> {
+    crate::blitz_hash(seed, data).to_vec()
+}
@@ -0,0 +1,74 @@
+//! Collision smoke test over every 2-4 letter lowercase ASCII string
+//! Doubles as a regression guard for the tail handling (all inputs are < 8 bytes)
+
+use blitzhash::blitz_hash64;
+use std::collections::HashSet;
+
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// 26^2 + 26^3 + 26^4 = 475_228 distinct short keys
+fn short_keys() -> Vec<Vec<u8>> {
+    let mut keys = Vec::new();
+    for len in 2..=4u32 {
+        for mut n in 0..ALPHABET.len().pow(len) {
+            let mut key = Vec::with_capacity(len as usize);
+            for _ in 0..len {
+                key.push(ALPHABET[n % ALPHABET.len()]);
+                n /= ALPHABET.len();
+            }
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// Number of colliding pairs among `values`
+fn colliding_pairs(mut values: Vec<u32>) -> u64 {
+    values.sort_unstable();
+    let mut pairs = 0;
+    let mut run = 1u64;
+    for i in 1..values.len() {
+        if values[i] == values[i - 1] {
+            run += 1;
+        } else {
+            pairs += run * (run - 1) / 2;
+            run = 1;
+        }
+    }
+    pairs + run * (run - 1) / 2
+}
+
+#[test]
+fn no_64bit_collisions_on_short_keys() {
+    let keys = short_keys();
+    for seed in [0, 0x9e3779b97f4a7c15] {
+        let mut seen = HashSet::with_capacity(keys.len());
+        for key in &keys {
+            assert!(
+                seen.insert(blitz_hash64(seed, key)),
+                "64-bit collision on {:?} (seed {})",
+                String::from_utf8_lossy(key),
+                seed
+            );
+        }
+    }
+}
+
+#[test]
+fn bounded_32bit_collisions_on_short_keys() {
+    let keys = short_keys();
+    let n = keys.len() as f64;
+    // Birthday bound: n^2 / 2^33 ~ 26 expected pairs; allow ~6 sigma of Poisson noise
+    let expected = n * (n - 1.0) / 2.0 / 2f64.powi(32);
+    let limit = (expected + 6.0 * expected.sqrt()).ceil() as u64;
+
+    let truncated = keys.iter().map(|key| blitz_hash64(0, key) as u32).collect();
+    let pairs = colliding_pairs(truncated);
+    assert!(
+        pairs <= limit,
+        "{} 32-bit collisions, expected ~{:.1} (limit {})",
+        pairs,
+        expected,
+        limit
+    );
+}
@@ -0,0 +1,45 @@
+#!/usr/bin/env python3
+"""
+BlitzHash Python binding smoke test
+Run after installing the wheel: maturin develop --release && python tests/python_smoke.py
+"""
+
+import blitzhash
+
+FOX = b'The quick brown fox jumps over the lazy dog'
+
+# Reference digests from the Rust blitz_hash
+VECTORS = [
+    (FOX, 0, 'bd90493fbdfe1b62878e0f6f520aecbb4be129d91ef80ebd9cf8277da8cb4c78'),
+    (FOX, 42, 'e857e2e456e9b1e8f62096da3aae9d25ee7d1a6a81ed0e53c074ff042fc7dcfc'),
+    (b'', 7, '1ea81754de7f16ad64ebde26c7b5734aea8eaaaad6d6439d3424c51cfdd590c1'),
+]
+
+def test_one_shot():
+    for data, seed, expected in VECTORS:
+        digest = blitzhash.blitz_hash(data, seed)
+        assert isinstance(digest, bytes) and len(digest) == 32
+        assert digest.hex() == expected, (data, seed, digest.hex())
+    assert blitzhash.blitz_hash(FOX) == blitzhash.blitz_hash(FOX, seed=0)
+
+def test_incremental():
+    for data, seed, expected in VECTORS:
+        hasher = blitzhash.BlitzHasher(seed)
+        for i in range(0, len(data), 5):
+            hasher.update(data[i:i + 5])
+        assert hasher.hexdigest() == expected
+        assert hasher.digest() == bytes.fromhex(expected)
+
+def test_digest_does_not_consume():
+    hasher = blitzhash.BlitzHasher(42)
+    hasher.update(FOX[:10])
+    first = hasher.digest()
+    hasher.update(FOX[10:])
+    assert first != hasher.digest()
+    assert hasher.hexdigest() == VECTORS[1][2]
+
+if __name__ == '__main__':
+    test_one_shot()
+    test_incremental()
+    test_digest_does_not_consume()
+    print('✅ Python smoke test passed')
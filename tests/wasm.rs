@@ -0,0 +1,28 @@
+//! wasm32 check against the native reference vector
+//! Run with `wasm-pack test --node --no-default-features --features wasm`
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use blitzhash::wasm::blitz_hash_wasm;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// blitz_hash(42, FOX) on x86_64
+const FOX_SEED_42: &str = "e857e2e456e9b1e8f62096da3aae9d25ee7d1a6a81ed0e53c074ff042fc7dcfc";
+
+#[wasm_bindgen_test]
+fn matches_native_reference() {
+    let digest = blitz_hash_wasm(42, b"The quick brown fox jumps over the lazy dog");
+    assert_eq!(digest.len(), 32);
+    assert_eq!(hex::encode(&digest), FOX_SEED_42);
+    assert_eq!(digest, blitzhash::blitz_hash(42, b"The quick brown fox jumps over the lazy dog"));
+}
+
+#[wasm_bindgen_test]
+fn parallel_falls_back_to_serial_blocks() {
+    // No threads on wasm32 - the block path still runs and stays deterministic
+    let data = vec![3u8; 1_500_000];
+    assert_eq!(
+        blitzhash::blitz_hash_parallel(0, &data, 8),
+        blitzhash::blitz_hash_parallel(0, &data, 1)
+    );
+}
@@ -35,7 +35,7 @@ def plot_throughput_comparison(df, output='bench_plot.png'):
     fig.suptitle('BlitzHash Performance Benchmark', fontsize=16, fontweight='bold')
     
     # bar chart -absolute throughput
-    colors = ['#e74c3c', '#3498db', '#2ecc71']
+    colors = ['#e74c3c', '#3498db', '#9b59b6', '#f39c12', '#2ecc71']
     bars = ax1.barh(summary.index, summary.values, color=colors[:len(summary)])
     ax1.set_xlabel('Throughput (MB/s)', fontsize=12)
     ax1.set_title('Absolute Throughput', fontsize=14)
@@ -49,7 +49,8 @@ def plot_throughput_comparison(df, output='bench_plot.png'):
                 ha='left', va='center', fontweight='bold', fontsize=10)
     
     # SPEEEEDup chart
-    baseline = summary.iloc[0]  # SHA-256 is first (sorted)
+    # SHA-256 baseline - not necessarily the slowest row (small keys can be slower)
+    baseline = summary['SHA-256'] if 'SHA-256' in summary else summary.iloc[0]
     speedup = summary / baseline
     
     bars2 = ax2.barh(speedup.index, speedup.values, color=colors[:len(speedup)])